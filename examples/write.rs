@@ -1,13 +1,13 @@
 use std::os::unix::io::AsRawFd;
-use std::{error::Error, fs::File, io::IoSlice};
+use std::{error::Error, fs::File};
 
 fn main() -> Result<(), Box<dyn Error>> {
     let (mut sq, mut cq, _) = urio::new(8)?;
     let file = File::create("hello.txt")?;
 
-    let message = b"Hello, urio!";
+    let message: &[u8] = b"Hello, urio!";
     sq.alloc_sqe()?
-        .packup_write_vectored(file.as_raw_fd(), &[IoSlice::new(message)], 0);
+        .packup_write_owned(file.as_raw_fd(), message, 0);
     sq.submit_and_wait(1)?;
 
     let cqe = cq.reap_cqe()?;
@@ -0,0 +1,620 @@
+use std::{
+    io,
+    mem::{self, ManuallyDrop},
+    ops::Deref,
+    slice,
+    sync::{
+        atomic::{AtomicU16, AtomicU8, Ordering},
+        Arc,
+    },
+};
+
+use bitflags::bitflags;
+
+use crate::{
+    op::{BufSelect, Slab},
+    queue::util::Mmap,
+    resultify,
+    sys::{
+        self, io_uring_buf, io_uring_buf_reg, IORING_REGISTER_PBUF_RING,
+        IORING_UNREGISTER_PBUF_RING, IOU_PBUF_RING_INC, IO_URING_BUF_RING_TAIL_OFFSET,
+    },
+    Cqe, Uring,
+};
+
+bitflags! {
+    /// Flags for registering a [`BufRing`].
+    pub struct BufRingFlags: u16 {
+        /// Consume the buffers incrementally. Each completion consumes only as
+        /// much of the selected buffer as it needs, and the rest of it is
+        /// left in the ring for the following completions, which is reported
+        /// by [`Cqe::buffer_more`].
+        ///
+        /// **Available since kernel 6.12.**
+        ///
+        /// [`Cqe::buffer_more`]: method@crate::Cqe::buffer_more
+        const INC = IOU_PBUF_RING_INC;
+    }
+}
+
+/// A ring of provided buffers shared with the kernel.
+///
+/// Like the [`BufferGroup`], the kernel selects one of the buffers in the ring
+/// when the data is actually ready. But the buffers are handed to the kernel
+/// by writing them to the ring mapped in the memory, rather than submitting
+/// SQEs, which makes refilling much cheaper.
+///
+/// A [`BufRing`] is registered by [`Registrar::register_buf_ring`] with all of
+/// its buffers in the ring. The selected buffer is taken out of the ring by
+/// [`get`], and it's returned to the ring by [`recycle`] when the caller is
+/// done with it. Several buffers can be returned at once by [`push`]ing them
+/// and making them visible to the kernel by a single [`commit`]. The ring is
+/// unregistered when it's dropped.
+///
+/// A ring can also queue the data to be sent by [`packup_send_bundle`]. Such
+/// a ring is registered by [`Registrar::register_buf_ring_empty`], and each
+/// buffer is pushed by [`push_with_len`] once it's filled with the data.
+///
+/// The ring keeps track of the buffers in it, which can't be accessed until
+/// they are taken out by [`get`] or [`bundle`]. If the ring is dropped while
+/// some of them are still in it, the memory of the buffers is leaked, since
+/// the kernel may write into it anytime.
+///
+/// The buffers can be returned from other threads as well. The buffer taken
+/// out by [`take`] is owned by the returned [`RingBuf`], which can be sent to
+/// another thread and returned to the ring by the [`BufRingProducer`] from
+/// [`producer`]. The producers put the buffers into the ring without locks,
+/// so they never wait for each other.
+///
+/// **Available since kernel 5.19.**
+///
+/// [`BufferGroup`]: struct@crate::op::BufferGroup
+/// [`Registrar::register_buf_ring`]: method@crate::Registrar::register_buf_ring
+/// [`get`]: method@Self::get
+/// [`bundle`]: method@Self::bundle
+/// [`recycle`]: method@Self::recycle
+/// [`push`]: method@Self::push
+/// [`commit`]: method@Self::commit
+/// [`packup_send_bundle`]: method@crate::Packer::packup_send_bundle
+/// [`Registrar::register_buf_ring_empty`]: method@crate::Registrar::register_buf_ring_empty
+/// [`push_with_len`]: method@Self::push_with_len
+/// [`take`]: method@Self::take
+/// [`producer`]: method@Self::producer
+pub struct BufRing {
+    uring: Arc<Uring>,
+    bgid: u16,
+    ring: Arc<Ring>,
+    // Offsets of the unconsumed parts of buffers, only for the incremental
+    // consumption.
+    offsets: Option<Box<[usize]>>,
+}
+
+// The buffer is out of the ring, and only accessed through the `BufRing`.
+const FREE: u8 = 0;
+// The buffer is in the ring, which may be written by the kernel.
+const IN_RING: u8 = 1;
+// The buffer is out of the ring, and owned by a `RingBuf`.
+const TAKEN: u8 = 2;
+
+/// The ring and the buffers, which are shared by the [`BufRing`], the
+/// producers and the buffers taken out of the ring.
+struct Ring {
+    mask: u16,
+    ring: Mmap,
+    slab: ManuallyDrop<Slab>,
+    // The state of each buffer.
+    states: Box<[AtomicU8]>,
+    // Positions in the ring where each buffer is put lastly.
+    positions: Box<[AtomicU16]>,
+    // The position next to the one put in each entry, which tells that the
+    // entry is written and can be made visible to the kernel.
+    written: Box<[AtomicU16]>,
+    // The position where the next buffer is put, which is ahead of the tail
+    // while the entries are being written.
+    reserved: AtomicU16,
+}
+
+impl BufRing {
+    pub(crate) fn new(
+        uring: Arc<Uring>,
+        bgid: u16,
+        entries: u16,
+        buf_len: usize,
+        flags: BufRingFlags,
+    ) -> io::Result<Self> {
+        if !entries.is_power_of_two() || buf_len > u32::MAX as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "entries must be a power of 2 and buf_len must fit in u32",
+            ));
+        }
+
+        let ring = Mmap::anonymous(entries as usize * mem::size_of::<io_uring_buf>())?;
+        let reg = io_uring_buf_reg {
+            ring_addr: *ring as _,
+            ring_entries: entries as _,
+            bgid,
+            flags: flags.bits(),
+            ..Default::default()
+        };
+        let ret = unsafe {
+            sys::io_uring_register(
+                uring.fd,
+                IORING_REGISTER_PBUF_RING,
+                &reg as *const _ as _,
+                1,
+            )
+        };
+        resultify(ret)?;
+
+        Ok(Self {
+            uring,
+            bgid,
+            ring: Arc::new(Ring {
+                mask: entries - 1,
+                ring,
+                slab: ManuallyDrop::new(Slab::new(entries, buf_len)),
+                states: atomics(entries),
+                positions: atomics(entries),
+                written: atomics(entries),
+                reserved: AtomicU16::new(0),
+            }),
+            offsets: if flags.contains(BufRingFlags::INC) {
+                Some(vec![0; entries as usize].into_boxed_slice())
+            } else {
+                None
+            },
+        })
+    }
+
+    /// Returns the id of the buffer group.
+    #[inline]
+    pub fn id(&self) -> u16 {
+        self.bgid
+    }
+
+    /// Returns the number of buffers in the ring.
+    #[allow(clippy::len_without_is_empty)]
+    #[inline]
+    pub fn len(&self) -> u16 {
+        self.ring.slab.nbufs
+    }
+
+    /// Returns the size of each buffer in the ring.
+    #[inline]
+    pub fn buf_len(&self) -> usize {
+        self.ring.slab.buf_len
+    }
+
+    /// Put the buffer identified by `bid` at the tail of the ring. It's not
+    /// visible to the kernel until [`commit`] is called, or a
+    /// [`BufRingProducer`] returns a buffer.
+    ///
+    /// The buffer must be taken out of the ring beforehand, so that the ring
+    /// never holds more buffers than its entries.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bid` is out of the ring, the buffer is already in the ring,
+    /// or it's owned by a [`RingBuf`].
+    ///
+    /// [`commit`]: method@Self::commit
+    #[inline]
+    pub fn push(&mut self, bid: u16) {
+        self.push_with_len(bid, self.buf_len());
+    }
+
+    /// Like [`push`], but only the first `len` bytes of the buffer are handed
+    /// to the kernel.
+    ///
+    /// It's used to queue the data to be sent by [`packup_send_bundle`],
+    /// which is written by [`buffer_mut`] beforehand.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bid` is out of the ring, the buffer is already in the ring,
+    /// it's owned by a [`RingBuf`], or `len` is larger than the [`buf_len`].
+    ///
+    /// [`push`]: method@Self::push
+    /// [`packup_send_bundle`]: method@crate::Packer::packup_send_bundle
+    /// [`buffer_mut`]: method@Self::buffer_mut
+    /// [`buf_len`]: method@Self::buf_len
+    pub fn push_with_len(&mut self, bid: u16, len: usize) {
+        assert!(bid < self.len(), "buffer id out of the ring");
+        assert!(len <= self.buf_len(), "length out of the buffer");
+        match self.ring.states[bid as usize].compare_exchange(
+            FREE,
+            IN_RING,
+            Ordering::Acquire,
+            Ordering::Relaxed,
+        ) {
+            Ok(_) => self.ring.put(bid, len),
+            Err(IN_RING) => panic!("the buffer is already in the ring"),
+            Err(_) => panic!("the buffer is owned by a RingBuf"),
+        }
+    }
+
+    /// Make the buffers [`push`]ed so far visible to the kernel.
+    ///
+    /// [`push`]: method@Self::push
+    #[inline]
+    pub fn commit(&mut self) {
+        self.ring.commit();
+    }
+
+    /// Return the buffer identified by `bid` to the ring. It's a shorthand of
+    /// [`push`] followed by [`commit`].
+    ///
+    /// [`push`]: method@Self::push
+    /// [`commit`]: method@Self::commit
+    #[inline]
+    pub fn recycle(&mut self, bid: u16) {
+        self.push(bid);
+        self.commit();
+    }
+
+    /// Returns a [`BufRingProducer`], which returns the buffers owned by
+    /// [`RingBuf`]s to the ring from any thread.
+    #[inline]
+    pub fn producer(&self) -> BufRingProducer {
+        BufRingProducer(Arc::clone(&self.ring))
+    }
+
+    /// Returns `len` bytes from `offset` of the buffer identified by `bid`, or
+    /// `None` if the buffer is in the ring or owned by a [`RingBuf`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bid` is out of the ring or the range is out of the buffer.
+    #[inline]
+    pub fn buffer(&self, bid: u16, offset: usize, len: usize) -> Option<&[u8]> {
+        let buf = self.ring.slab.buffer(bid, offset, len);
+        if self.ring.state(bid) == FREE {
+            Some(buf)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the whole buffer identified by `bid` to be written, or `None`
+    /// if the buffer is in the ring or owned by a [`RingBuf`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bid` is out of the ring.
+    #[inline]
+    pub fn buffer_mut(&mut self, bid: u16) -> Option<&mut [u8]> {
+        assert!(bid < self.len(), "buffer id out of the ring");
+        if self.ring.state(bid) == FREE {
+            // Only the `BufRing` accesses the buffer out of the ring, which is
+            // borrowed mutably.
+            let ptr = self.ring.slab.buf_ptr(bid);
+            Some(unsafe { slice::from_raw_parts_mut(ptr, self.buf_len()) })
+        } else {
+            None
+        }
+    }
+
+    /// Take the buffer selected by the operation which produced the `cqe` out
+    /// of the ring, and returns the data in it.
+    ///
+    /// If the ring consumes buffers incrementally, the data is placed right
+    /// after the data of the previous event which selected the same buffer,
+    /// and the buffer is left in the ring while [`Cqe::buffer_more`] returns
+    /// `true`. So the events must be passed in order.
+    ///
+    /// `None` is returned if no buffer is selected from this ring (e.g. the
+    /// operation failed, or it selects from another group). The data is
+    /// truncated to the end of the buffer if the result is larger (e.g.
+    /// `MSG_TRUNC`).
+    ///
+    /// [`Cqe::buffer_more`]: method@crate::Cqe::buffer_more
+    pub fn get(&mut self, cqe: &Cqe) -> Option<&[u8]> {
+        let (bid, offset, len) = self.take_out(cqe, FREE)?;
+        // The consumed part is never written by the kernel again, even if the
+        // rest of the buffer is still in the ring.
+        Some(self.ring.slab.buffer(bid, offset, len))
+    }
+
+    /// Like [`get`], but the buffer is owned by the returned [`RingBuf`],
+    /// which can be sent to another thread, and returned to the ring by a
+    /// [`BufRingProducer`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ring consumes buffers incrementally, whose buffers are
+    /// left in the ring until they are consumed entirely.
+    ///
+    /// [`get`]: method@Self::get
+    pub fn take(&mut self, cqe: &Cqe) -> Option<RingBuf> {
+        assert!(
+            self.offsets.is_none(),
+            "the ring consumes buffers incrementally"
+        );
+        let (bid, _, len) = self.take_out(cqe, TAKEN)?;
+        Some(RingBuf {
+            ring: Arc::clone(&self.ring),
+            bid,
+            len,
+        })
+    }
+
+    /// Returns an iterator over the buffers consumed by the bundled operation
+    /// which produced the `cqe` (e.g. [`packup_recv_bundle`],
+    /// [`packup_send_bundle`]), along with the data in them.
+    ///
+    /// The buffers are consumed from the ring in order, starting from the one
+    /// given by [`Cqe::buffer_id`], and they are taken out of the ring. The
+    /// iterator is empty if no buffer is selected from this ring.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ring consumes buffers incrementally, which can't tell
+    /// how the data is spread over the buffers.
+    ///
+    /// [`packup_recv_bundle`]: method@crate::Packer::packup_recv_bundle
+    /// [`packup_send_bundle`]: method@crate::Packer::packup_send_bundle
+    /// [`Cqe::buffer_id`]: method@crate::Cqe::buffer_id
+    pub fn bundle(&mut self, cqe: &Cqe) -> Bundle<'_> {
+        assert!(
+            self.offsets.is_none(),
+            "the ring consumes buffers incrementally"
+        );
+        let bid = cqe.buffer_id_in(self.uring.id(), self.bgid);
+        let (pos, remaining) = match (bid, cqe.result()) {
+            (Some(bid), Ok(len)) => (self.ring.position(bid), len as usize),
+            _ => (0, 0),
+        };
+        let (mut next, mut rest) = (pos, remaining);
+        while rest > 0 {
+            let (bid, len) = self.ring.consumed(next, rest);
+            self.ring.states[bid as usize].store(FREE, Ordering::Release);
+            next = next.wrapping_add(1);
+            rest -= len;
+        }
+        Bundle {
+            ring: &self.ring,
+            pos,
+            remaining,
+        }
+    }
+
+    // Take the buffer selected by the operation which produced the `cqe` out
+    // of the ring into the `state`, unless it's left in the ring. Returns the
+    // id of the buffer and the range of the data in it.
+    fn take_out(&mut self, cqe: &Cqe, state: u8) -> Option<(u16, usize, usize)> {
+        let bid = cqe.buffer_id_in(self.uring.id(), self.bgid)?;
+        assert!(bid < self.len(), "buffer id out of the ring");
+        let len = cqe.result().ok()? as usize;
+        let offset = match &mut self.offsets {
+            Some(offsets) => {
+                let offset = offsets[bid as usize];
+                offsets[bid as usize] += len;
+                offset
+            }
+            None => 0,
+        };
+        if !cqe.buffer_more() {
+            if let Some(offsets) = &mut self.offsets {
+                // The buffer is consumed entirely.
+                offsets[bid as usize] = 0;
+            }
+            self.ring.states[bid as usize].store(state, Ordering::Release);
+        }
+        let len = len.min(self.buf_len().saturating_sub(offset));
+        Some((bid, offset, len))
+    }
+}
+
+impl Ring {
+    #[inline]
+    fn state(&self, bid: u16) -> u8 {
+        self.states[bid as usize].load(Ordering::Acquire)
+    }
+
+    #[inline]
+    fn position(&self, bid: u16) -> u16 {
+        self.positions[bid as usize].load(Ordering::Relaxed)
+    }
+
+    // Put the first `len` bytes of the buffer identified by `bid`, which is
+    // just marked as in the ring, into the entry reserved for it.
+    fn put(&self, bid: u16, len: usize) {
+        // Each buffer is put only once until it's consumed, so the ring never
+        // runs over the entries which the kernel hasn't consumed.
+        let pos = self.reserved.fetch_add(1, Ordering::Relaxed);
+        unsafe {
+            let buf = self.entry(pos);
+            // Don't write the `resv`, which overlays the tail of the ring.
+            (*buf).addr = self.slab.buf_ptr(bid) as _;
+            (*buf).len = len as _;
+            (*buf).bid = bid;
+        }
+        self.positions[bid as usize].store(pos, Ordering::Relaxed);
+        self.written[(pos & self.mask) as usize].store(pos.wrapping_add(1), Ordering::SeqCst);
+    }
+
+    // Advance the tail over the written entries, which may be written by other
+    // producers.
+    fn commit(&self) {
+        // The tail is loaded after the entry is marked as written, and vice
+        // versa, so that either this or the producer of the entry advances the
+        // tail over it.
+        let tail = self.tail();
+        let mut pos = tail.load(Ordering::SeqCst);
+        while self.written[(pos & self.mask) as usize].load(Ordering::SeqCst) == pos.wrapping_add(1)
+        {
+            let next = pos.wrapping_add(1);
+            pos = match tail.compare_exchange(pos, next, Ordering::SeqCst, Ordering::SeqCst) {
+                Ok(_) => next,
+                Err(pos) => pos,
+            };
+        }
+    }
+
+    // Returns the id of the buffer at `pos` in the ring, and the length of the
+    // data in it out of the `remaining` data.
+    #[inline]
+    fn consumed(&self, pos: u16, remaining: usize) -> (u16, usize) {
+        unsafe {
+            let buf = self.entry(pos);
+            ((*buf).bid, ((*buf).len as usize).min(remaining))
+        }
+    }
+
+    #[inline]
+    fn entry(&self, pos: u16) -> *mut io_uring_buf {
+        unsafe { (*self.ring as *mut io_uring_buf).add((pos & self.mask) as _) }
+    }
+
+    #[inline]
+    fn tail(&self) -> &AtomicU16 {
+        unsafe { &*((*self.ring as *const u8).add(IO_URING_BUF_RING_TAIL_OFFSET) as *const _) }
+    }
+}
+
+impl Drop for Ring {
+    fn drop(&mut self) {
+        // The operations which selected the buffers in the ring may still
+        // write into them, so they are leaked.
+        if self
+            .states
+            .iter_mut()
+            .all(|state| *state.get_mut() != IN_RING)
+        {
+            unsafe { ManuallyDrop::drop(&mut self.slab) };
+        }
+    }
+}
+
+#[inline]
+fn atomics<T: Default>(n: u16) -> Box<[T]> {
+    (0..n).map(|_| T::default()).collect()
+}
+
+/// A buffer taken out of a [`BufRing`] by [`BufRing::take`], which dereferences
+/// to the data in it.
+///
+/// It's returned to the ring by [`BufRingProducer::push`]. Otherwise, it can
+/// be accessed by the [`BufRing`] again once it's dropped.
+///
+/// [`BufRing::take`]: method@crate::BufRing::take
+/// [`BufRingProducer::push`]: method@crate::BufRingProducer::push
+pub struct RingBuf {
+    ring: Arc<Ring>,
+    bid: u16,
+    len: usize,
+}
+
+impl RingBuf {
+    /// Returns the id of the buffer.
+    #[inline]
+    pub fn id(&self) -> u16 {
+        self.bid
+    }
+}
+
+impl Deref for RingBuf {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        self.ring.slab.buffer(self.bid, 0, self.len)
+    }
+}
+
+impl Drop for RingBuf {
+    #[inline]
+    fn drop(&mut self) {
+        // Unless it's returned to the ring.
+        let _ = self.ring.states[self.bid as usize].compare_exchange(
+            TAKEN,
+            FREE,
+            Ordering::Release,
+            Ordering::Relaxed,
+        );
+    }
+}
+
+/// A handle to return the buffers owned by [`RingBuf`]s to a [`BufRing`],
+/// which can be cloned and shared between threads.
+///
+/// This is created by [`BufRing::producer`].
+///
+/// [`BufRing::producer`]: method@crate::BufRing::producer
+#[derive(Clone)]
+pub struct BufRingProducer(Arc<Ring>);
+
+impl BufRingProducer {
+    /// Return the buffer owned by the `buf` to the ring, and make it visible
+    /// to the kernel along with the buffers returned so far.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `buf` is taken out of another ring.
+    pub fn push(&self, buf: RingBuf) {
+        assert!(
+            Arc::ptr_eq(&self.0, &buf.ring),
+            "the buffer is taken out of another ring"
+        );
+        // The buffer isn't freed by the `RingBuf`, since it's in the ring.
+        self.0.states[buf.bid as usize].store(IN_RING, Ordering::Relaxed);
+        self.0.put(buf.bid, self.0.slab.buf_len);
+        self.0.commit();
+    }
+}
+
+/// An iterator over the buffers consumed by a bundled operation, which yields
+/// the buffer id and the data in the buffer.
+///
+/// This is created by [`BufRing::bundle`].
+///
+/// [`BufRing::bundle`]: method@crate::BufRing::bundle
+pub struct Bundle<'a> {
+    ring: &'a Ring,
+    pos: u16,
+    remaining: usize,
+}
+
+impl<'a> Iterator for Bundle<'a> {
+    type Item = (u16, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let (bid, len) = self.ring.consumed(self.pos, self.remaining);
+        self.pos = self.pos.wrapping_add(1);
+        self.remaining -= len;
+        Some((bid, self.ring.slab.buffer(bid, 0, len)))
+    }
+}
+
+impl BufSelect for BufRing {
+    #[inline]
+    fn group_id(&self) -> u16 {
+        self.bgid
+    }
+
+    #[inline]
+    fn buf_len(&self) -> usize {
+        self.ring.slab.buf_len
+    }
+}
+
+impl Drop for BufRing {
+    fn drop(&mut self) {
+        let reg = io_uring_buf_reg {
+            bgid: self.bgid,
+            ..Default::default()
+        };
+        unsafe {
+            sys::io_uring_register(
+                self.uring.fd,
+                IORING_UNREGISTER_PBUF_RING,
+                &reg as *const _ as _,
+                1,
+            );
+        }
+    }
+}
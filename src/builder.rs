@@ -2,10 +2,11 @@ use std::io;
 use std::sync::Arc;
 
 use crate::{
+    next_uring_id,
     queue::{self, Cq, Sq},
     register::Registrar,
     resultify,
-    sys::{self, io_uring_params},
+    sys::{self, io_uring_params, IORING_SETUP_CQE32, IORING_SETUP_SQE128},
     Uring,
 };
 
@@ -35,6 +36,29 @@ impl Builder {
         }
     }
 
+    /// Make each SQE 128 bytes long instead of 64 bytes. The extra 64 bytes
+    /// extend the command area of [`packup_uring_cmd`], which is required by
+    /// some drivers (e.g. NVMe passthrough).
+    ///
+    /// **Available since kernel 5.19.**
+    ///
+    /// [`packup_uring_cmd`]: method@crate::Packer::packup_uring_cmd
+    pub fn big_sqe(&mut self) -> &mut Self {
+        self.p.flags |= IORING_SETUP_SQE128;
+        self
+    }
+
+    /// Make each CQE 32 bytes long instead of 16 bytes. The extra 16 bytes
+    /// are returned by [`Cqe::big_cqe`].
+    ///
+    /// **Available since kernel 5.19.**
+    ///
+    /// [`Cqe::big_cqe`]: method@crate::Cqe::big_cqe
+    pub fn big_cqe(&mut self) -> &mut Self {
+        self.p.flags |= IORING_SETUP_CQE32;
+        self
+    }
+
     /// Build the configured [`Sq`], [`Cq`] and [`Registrar`].
     pub fn build(&mut self) -> io::Result<(Sq, Cq, Registrar)> {
         let fd = unsafe { sys::io_uring_setup(self.entries, &mut self.p) };
@@ -47,9 +71,11 @@ impl Builder {
             },
             |(sqring, cqring, sqes)| {
                 let uring = Arc::new(Uring {
+                    id: next_uring_id(),
                     fd,
                     flags: self.p.flags,
                     features: self.p.features,
+                    owned: Default::default(),
                 });
                 Ok((
                     Sq::new(Arc::clone(&uring), sqring, self.p.sq_off, sqes),
@@ -3,6 +3,9 @@
 //! urio is a [io_uring](https://kernel.dk/io_uring.pdf) library written in
 //! Rust. It provides a **safe** Rust-friendly interface.
 
+mod buf_ring;
+pub use buf_ring::{BufRing, BufRingFlags, BufRingProducer, Bundle, RingBuf};
+
 mod builder;
 pub use builder::Builder;
 
@@ -10,7 +13,7 @@ pub mod op;
 pub use op::{cqe::Cqe, sqe::Packer};
 
 mod queue;
-pub use queue::{Cq, Reaper, Sq};
+pub use queue::{Cq, Reaper, Scope, Sq};
 
 mod register;
 pub use register::Registrar;
@@ -20,9 +23,15 @@ mod sys;
 use std::{
     io,
     os::unix::io::{AsRawFd, RawFd},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex, MutexGuard, PoisonError,
+    },
 };
 
-use sys::{IORING_SETUP_IOPOLL, IORING_SETUP_SQPOLL};
+use op::Registry;
+
+use sys::{IORING_SETUP_CQE32, IORING_SETUP_IOPOLL, IORING_SETUP_SQE128, IORING_SETUP_SQPOLL};
 
 /// Create a new io_uring instance with given `entries` entries and default
 /// configuration values. On success, [`Sq`], [`Cq`] and [`Registrar`] will be
@@ -38,9 +47,14 @@ pub fn new(entries: u32) -> io::Result<(Sq, Cq, Registrar)> {
 
 /// Interface for getting information about the io_uring instance.
 pub struct Uring {
+    // Unlike the file descriptor, it's never reused by another instance.
+    id: u64,
     fd: RawFd,
     flags: u32,
+    #[allow(dead_code)]
     features: u32,
+    // Buffers owned by the operations in flight.
+    owned: Mutex<Registry>,
 }
 
 impl Uring {
@@ -55,6 +69,43 @@ impl Uring {
     pub fn has_sqpoll(&self) -> bool {
         self.flags & IORING_SETUP_SQPOLL != 0
     }
+
+    /// Return `true` if the SQEs are 128 bytes long, which have the extra
+    /// command area for [`packup_uring_cmd`].
+    ///
+    /// [`packup_uring_cmd`]: method@crate::Packer::packup_uring_cmd
+    #[inline]
+    pub fn has_big_sqe(&self) -> bool {
+        self.flags & IORING_SETUP_SQE128 != 0
+    }
+
+    /// Return `true` if the CQEs are 32 bytes long, which carry the extra
+    /// data returned by [`Cqe::big_cqe`].
+    ///
+    /// [`Cqe::big_cqe`]: method@crate::Cqe::big_cqe
+    #[inline]
+    pub fn has_big_cqe(&self) -> bool {
+        self.flags & IORING_SETUP_CQE32 != 0
+    }
+
+    /// Returns the id which identifies the instance in the process.
+    #[inline]
+    pub(crate) fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Returns the buffers owned by the operations in flight.
+    #[inline]
+    pub(crate) fn owned(&self) -> MutexGuard<'_, Registry> {
+        self.owned.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Returns the id for a new [`Uring`].
+#[inline]
+pub(crate) fn next_uring_id() -> u64 {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+    NEXT_ID.fetch_add(1, Ordering::Relaxed)
 }
 
 impl Drop for Uring {
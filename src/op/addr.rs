@@ -0,0 +1,186 @@
+use std::{
+    ffi::OsStr,
+    io, mem,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+    os::unix::ffi::OsStrExt,
+    path::Path,
+    ptr,
+};
+
+use libc::{
+    sa_family_t, sockaddr, sockaddr_in, sockaddr_in6, sockaddr_storage, sockaddr_un, socklen_t,
+};
+
+/// An owned storage of a socket address.
+///
+/// It's large enough to hold any kind of socket address (e.g. IPv4, IPv6,
+/// Unix domain), so it can be used either as an input of the operation (e.g.
+/// [`packup_connect`]) or as an output which will be filled by the kernel
+/// (e.g. [`packup_accept`]).
+///
+/// [`packup_connect`]: method@crate::Packer::packup_connect
+/// [`packup_accept`]: method@crate::Packer::packup_accept
+#[derive(Clone, Copy)]
+pub struct SockAddrStorage {
+    storage: sockaddr_storage,
+    len: socklen_t,
+}
+
+impl SockAddrStorage {
+    /// Create a new empty [`SockAddrStorage`] which can hold any kind of socket
+    /// address.
+    pub fn new() -> Self {
+        Self {
+            storage: unsafe { mem::zeroed() },
+            len: mem::size_of::<sockaddr_storage>() as _,
+        }
+    }
+
+    /// Create a new [`SockAddrStorage`] holding the Unix domain socket address
+    /// bound to the filesystem `path`.
+    ///
+    /// # Errors
+    ///
+    /// If the `path` is too long to fit in the `sun_path`, then an error is
+    /// returned.
+    pub fn from_unix_path<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref().as_os_str().as_bytes();
+        let mut addr: sockaddr_un = unsafe { mem::zeroed() };
+        // The last byte of `sun_path` is reserved for the nul terminator.
+        if path.len() >= addr.sun_path.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "path must be shorter than SUN_LEN",
+            ));
+        }
+        addr.sun_family = libc::AF_UNIX as _;
+        for (dst, src) in addr.sun_path.iter_mut().zip(path) {
+            *dst = *src as _;
+        }
+
+        let len = mem::size_of::<sa_family_t>() + path.len() + 1;
+        Ok(unsafe { Self::from_raw(&addr as *const _ as _, len as _) })
+    }
+
+    /// Copy `len` bytes of the socket address pointed to by `addr`.
+    pub(crate) unsafe fn from_raw(addr: *const sockaddr, len: socklen_t) -> Self {
+        let mut this = Self::new();
+        ptr::copy_nonoverlapping(
+            addr as *const u8,
+            &mut this.storage as *mut _ as *mut u8,
+            len as _,
+        );
+        this.len = len;
+        this
+    }
+
+    /// Returns the address family of the socket address.
+    #[inline]
+    pub fn family(&self) -> sa_family_t {
+        self.storage.ss_family
+    }
+
+    /// Returns the length of the socket address.
+    #[allow(clippy::len_without_is_empty)]
+    #[inline]
+    pub fn len(&self) -> socklen_t {
+        self.len
+    }
+
+    /// Returns a raw pointer to the socket address.
+    #[inline]
+    pub fn as_ptr(&self) -> *const sockaddr {
+        &self.storage as *const _ as _
+    }
+
+    /// Returns a raw mutable pointer to the socket address.
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut sockaddr {
+        &mut self.storage as *mut _ as _
+    }
+
+    /// Returns a raw mutable pointer to the length of the socket address.
+    #[inline]
+    pub(crate) fn len_mut_ptr(&mut self) -> *mut socklen_t {
+        &mut self.len
+    }
+
+    /// Returns the internet socket address if it holds one of `AF_INET` or
+    /// `AF_INET6` addresses.
+    pub fn as_socket_addr(&self) -> Option<SocketAddr> {
+        match self.family() as _ {
+            libc::AF_INET => {
+                let addr = unsafe { &*(self.as_ptr() as *const sockaddr_in) };
+                Some(SocketAddr::V4(SocketAddrV4::new(
+                    Ipv4Addr::from(u32::from_be(addr.sin_addr.s_addr)),
+                    u16::from_be(addr.sin_port),
+                )))
+            }
+            libc::AF_INET6 => {
+                let addr = unsafe { &*(self.as_ptr() as *const sockaddr_in6) };
+                Some(SocketAddr::V6(SocketAddrV6::new(
+                    Ipv6Addr::from(addr.sin6_addr.s6_addr),
+                    u16::from_be(addr.sin6_port),
+                    addr.sin6_flowinfo,
+                    addr.sin6_scope_id,
+                )))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the filesystem path if it holds a pathname Unix domain socket
+    /// address.
+    ///
+    /// `None` is returned for unnamed or abstract Unix domain socket
+    /// addresses.
+    pub fn as_unix_path(&self) -> Option<&Path> {
+        if self.family() as i32 != libc::AF_UNIX {
+            return None;
+        }
+        let addr = unsafe { &*(self.as_ptr() as *const sockaddr_un) };
+        let len = (self.len as usize).checked_sub(mem::size_of::<sa_family_t>())?;
+        let path = unsafe {
+            std::slice::from_raw_parts(
+                addr.sun_path.as_ptr() as *const u8,
+                len.min(addr.sun_path.len()),
+            )
+        };
+        match path.first() {
+            None | Some(0) => None,
+            Some(_) => {
+                let end = path.iter().position(|&b| b == 0).unwrap_or(path.len());
+                Some(Path::new(OsStr::from_bytes(&path[..end])))
+            }
+        }
+    }
+}
+
+impl Default for SockAddrStorage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<SocketAddr> for SockAddrStorage {
+    fn from(addr: SocketAddr) -> Self {
+        match addr {
+            SocketAddr::V4(addr) => {
+                let mut sin: sockaddr_in = unsafe { mem::zeroed() };
+                sin.sin_family = libc::AF_INET as _;
+                sin.sin_port = addr.port().to_be();
+                sin.sin_addr.s_addr = u32::from(*addr.ip()).to_be();
+                unsafe { Self::from_raw(&sin as *const _ as _, mem::size_of_val(&sin) as _) }
+            }
+            SocketAddr::V6(addr) => {
+                let mut sin6: sockaddr_in6 = unsafe { mem::zeroed() };
+                sin6.sin6_family = libc::AF_INET6 as _;
+                sin6.sin6_port = addr.port().to_be();
+                sin6.sin6_addr.s6_addr = addr.ip().octets();
+                sin6.sin6_flowinfo = addr.flowinfo();
+                sin6.sin6_scope_id = addr.scope_id();
+                unsafe { Self::from_raw(&sin6 as *const _ as _, mem::size_of_val(&sin6) as _) }
+            }
+        }
+    }
+}
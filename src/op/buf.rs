@@ -0,0 +1,232 @@
+use std::{mem::ManuallyDrop, ptr, slice};
+
+use crate::{Cqe, Uring};
+
+/// A source of provided buffers, which operations can select a buffer from
+/// (e.g. [`packup_read_select`], [`packup_recv_select`]).
+///
+/// [`packup_read_select`]: method@crate::Packer::packup_read_select
+/// [`packup_recv_select`]: method@crate::Packer::packup_recv_select
+pub trait BufSelect {
+    /// Returns the id of the buffer group.
+    fn group_id(&self) -> u16;
+
+    /// Returns the size of each buffer in the group.
+    fn buf_len(&self) -> usize;
+}
+
+/// A group of equally-sized buffers, which can be provided to the kernel.
+///
+/// Instead of passing a buffer when the operation is submitted, the kernel
+/// selects one of the provided buffers when the data is actually ready, which
+/// saves memory for the operations which may wait long (e.g. receiving from a
+/// socket). The group is identified by the `bgid`, and each buffer in the group
+/// is identified by its index, the buffer id.
+///
+/// The buffers are handed to the kernel by [`packup_provide_buffers`], and
+/// selected by the operations like [`packup_read_select`] and
+/// [`packup_recv_select`]. Once the operation is completed, the selected
+/// buffer is taken back by [`get`], and it should be recycled by
+/// [`packup_provide_buffer`] when the caller is done with it.
+///
+/// The group keeps track of the buffers provided to the kernel, which can't
+/// be accessed until they are taken back. If the group is dropped while some
+/// of them are still provided, the memory of the buffers is leaked, since the
+/// kernel may write into it anytime. The buffers removed by
+/// [`packup_remove_buffers`] are still considered provided, because the ids
+/// of them are unknown.
+///
+/// See also [`BufRing`], which provides buffers without submitting SQEs.
+///
+/// **Available since kernel 5.7.**
+///
+/// [`packup_provide_buffers`]: method@crate::Packer::packup_provide_buffers
+/// [`packup_provide_buffer`]: method@crate::Packer::packup_provide_buffer
+/// [`packup_remove_buffers`]: method@crate::Packer::packup_remove_buffers
+/// [`packup_read_select`]: method@crate::Packer::packup_read_select
+/// [`packup_recv_select`]: method@crate::Packer::packup_recv_select
+/// [`get`]: method@Self::get
+/// [`BufRing`]: struct@crate::BufRing
+pub struct BufferGroup {
+    bgid: u16,
+    // The id of the io_uring instance which the buffers are provided to.
+    uring: Option<u64>,
+    slab: ManuallyDrop<Slab>,
+    // Whether each buffer is provided to the kernel.
+    provided: Vec<bool>,
+}
+
+impl BufferGroup {
+    /// Create a new [`BufferGroup`] identified by the `bgid`, which consists
+    /// of `nbufs` buffers of `buf_len` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buf_len` doesn't fit in u32, or the total size of the
+    /// buffers overflows.
+    pub fn new(bgid: u16, nbufs: u16, buf_len: usize) -> Self {
+        assert!(buf_len <= u32::MAX as usize, "buf_len must fit in u32");
+        Self {
+            bgid,
+            uring: None,
+            slab: ManuallyDrop::new(Slab::new(nbufs, buf_len)),
+            provided: vec![false; nbufs as usize],
+        }
+    }
+
+    /// Returns the id of the group.
+    #[inline]
+    pub fn id(&self) -> u16 {
+        self.bgid
+    }
+
+    /// Returns the number of buffers in the group.
+    #[allow(clippy::len_without_is_empty)]
+    #[inline]
+    pub fn len(&self) -> u16 {
+        self.slab.nbufs
+    }
+
+    /// Returns the size of each buffer in the group.
+    #[inline]
+    pub fn buf_len(&self) -> usize {
+        self.slab.buf_len
+    }
+
+    /// Returns the first `len` bytes of the buffer identified by `bid`, or
+    /// `None` if the buffer is provided to the kernel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bid` is out of the group or `len` is larger than the
+    /// [`buf_len`].
+    ///
+    /// [`buf_len`]: method@Self::buf_len
+    #[inline]
+    pub fn buffer(&self, bid: u16, len: usize) -> Option<&[u8]> {
+        let buf = self.slab.buffer(bid, 0, len);
+        if self.provided[bid as usize] {
+            None
+        } else {
+            Some(buf)
+        }
+    }
+
+    /// Take back the buffer selected by the operation which produced the
+    /// `cqe`, and returns the data in it.
+    ///
+    /// `None` is returned if no buffer is selected from this group by the
+    /// io_uring instance which the group is provided to (e.g. the operation
+    /// failed, or it selects from another group). The data is truncated to
+    /// the [`buf_len`] if the result is larger (e.g. `MSG_TRUNC`).
+    ///
+    /// [`buf_len`]: method@Self::buf_len
+    #[inline]
+    pub fn get(&mut self, cqe: &Cqe) -> Option<&[u8]> {
+        let bid = cqe.buffer_id_in(self.uring?, self.bgid)?;
+        let len = (cqe.result().ok()? as usize).min(self.buf_len());
+        assert!(bid < self.len(), "buffer id out of the group");
+        self.provided[bid as usize] = false;
+        self.buffer(bid, len)
+    }
+
+    /// Mark the buffers from `bid` up to `nbufs` as provided to the kernel of
+    /// the `uring`. Returns the pointer to the first buffer.
+    pub(crate) fn provide(&mut self, uring: &Uring, bid: u16, nbufs: u16) -> *mut u8 {
+        let range = bid as usize..bid as usize + nbufs as usize;
+        assert!(
+            range.end <= self.provided.len(),
+            "buffer id out of the group"
+        );
+        assert!(
+            self.provided[range.clone()]
+                .iter()
+                .all(|&provided| !provided),
+            "the buffer is already provided"
+        );
+        if self.provided.iter().any(|&provided| provided) {
+            assert_eq!(
+                self.uring,
+                Some(uring.id()),
+                "the group is provided to another io_uring instance"
+            );
+        }
+        self.uring = Some(uring.id());
+        for provided in &mut self.provided[range] {
+            *provided = true;
+        }
+        self.slab.buf_ptr(bid)
+    }
+}
+
+impl Drop for BufferGroup {
+    fn drop(&mut self) {
+        // The kernel may still write into the provided buffers, so they are
+        // leaked.
+        if self.provided.iter().all(|&provided| !provided) {
+            unsafe { ManuallyDrop::drop(&mut self.slab) };
+        }
+    }
+}
+
+impl BufSelect for BufferGroup {
+    #[inline]
+    fn group_id(&self) -> u16 {
+        self.bgid
+    }
+
+    #[inline]
+    fn buf_len(&self) -> usize {
+        self.slab.buf_len
+    }
+}
+
+/// `nbufs` equally-sized buffers in a contiguous memory, which is shared with
+/// the kernel.
+pub(crate) struct Slab {
+    pub(crate) nbufs: u16,
+    pub(crate) buf_len: usize,
+    // The memory is written by the kernel, so it's only accessed through the
+    // raw pointer.
+    ptr: *mut u8,
+}
+
+impl Slab {
+    pub(crate) fn new(nbufs: u16, buf_len: usize) -> Self {
+        let size = (nbufs as usize)
+            .checked_mul(buf_len)
+            .expect("the total size of the buffers overflows");
+        let slab = vec![0u8; size].into_boxed_slice();
+        Self {
+            nbufs,
+            buf_len,
+            ptr: Box::into_raw(slab) as _,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn buffer(&self, bid: u16, offset: usize, len: usize) -> &[u8] {
+        assert!(bid < self.nbufs, "buffer id out of the group");
+        assert!(offset + len <= self.buf_len, "length out of the buffer");
+        unsafe { slice::from_raw_parts(self.buf_ptr(bid).add(offset), len) }
+    }
+
+    #[inline]
+    pub(crate) fn buf_ptr(&self, bid: u16) -> *mut u8 {
+        unsafe { self.ptr.add(bid as usize * self.buf_len) }
+    }
+}
+
+impl Drop for Slab {
+    fn drop(&mut self) {
+        unsafe {
+            drop(Box::from_raw(ptr::slice_from_raw_parts_mut(
+                self.ptr,
+                self.nbufs as usize * self.buf_len,
+            )));
+        }
+    }
+}
+
+unsafe impl Send for Slab {}
+unsafe impl Sync for Slab {}
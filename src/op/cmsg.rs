@@ -0,0 +1,253 @@
+use std::{
+    convert::TryInto,
+    mem,
+    os::unix::io::{FromRawFd, OwnedFd, RawFd},
+    ptr, slice,
+};
+
+use libc::{c_int, cmsghdr, ucred, CMSG_LEN, CMSG_SPACE, SCM_CREDENTIALS, SCM_RIGHTS, SOL_SOCKET};
+
+/// The credentials of a process, sent or received with `SCM_CREDENTIALS`.
+///
+/// See unix(7) for details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Credentials {
+    /// Process ID of the sending process.
+    pub pid: libc::pid_t,
+    /// User ID of the sending process.
+    pub uid: libc::uid_t,
+    /// Group ID of the sending process.
+    pub gid: libc::gid_t,
+}
+
+impl Credentials {
+    /// Returns the credentials of the current process.
+    pub fn current() -> Self {
+        unsafe {
+            Self {
+                pid: libc::getpid(),
+                uid: libc::getuid(),
+                gid: libc::getgid(),
+            }
+        }
+    }
+}
+
+/// A received control message (ancillary data), decoded from a [`CmsgBuf`].
+#[derive(Debug)]
+pub enum ControlMessage {
+    /// File descriptors passed with `SCM_RIGHTS`. They are owned by the
+    /// receiver and closed when dropped.
+    ScmRights(Vec<OwnedFd>),
+    /// Process credentials passed with `SCM_CREDENTIALS`.
+    ScmCredentials(Credentials),
+    /// Any other control message which is not decoded.
+    Unknown {
+        /// The originating protocol.
+        level: c_int,
+        /// The protocol-specific type.
+        ty: c_int,
+        /// The undecoded data.
+        data: Vec<u8>,
+    },
+}
+
+/// A properly aligned buffer of control messages (ancillary data), which can
+/// be plugged into a [`MsgHdr`] by [`cmsgs`] or [`cmsgs_mut`].
+///
+/// For sending, control messages are encoded into the buffer by [`push_rights`]
+/// and [`push_credentials`]. For receiving, a buffer created by
+/// [`with_capacity`] is filled in by the kernel, and it's decoded by
+/// [`decode`] once the operation is completed.
+///
+/// [`MsgHdr`]: struct@crate::op::MsgHdr
+/// [`cmsgs`]: method@crate::op::MsgHdr::cmsgs
+/// [`cmsgs_mut`]: method@crate::op::MsgHdr::cmsgs_mut
+/// [`push_rights`]: method@Self::push_rights
+/// [`push_credentials`]: method@Self::push_credentials
+/// [`with_capacity`]: method@Self::with_capacity
+/// [`decode`]: method@Self::decode
+#[derive(Debug, Default)]
+pub struct CmsgBuf {
+    // `u64` keeps every `cmsghdr` in the buffer aligned.
+    buf: Vec<u64>,
+    len: usize,
+}
+
+impl CmsgBuf {
+    /// Create a new empty [`CmsgBuf`] for sending control messages.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new [`CmsgBuf`] which can receive control messages up to
+    /// `capacity` bytes.
+    ///
+    /// See [`space_for_rights`] and [`space_for_credentials`] to get the
+    /// required capacity.
+    ///
+    /// [`space_for_rights`]: method@Self::space_for_rights
+    /// [`space_for_credentials`]: method@Self::space_for_credentials
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buf: vec![0; capacity.div_ceil(8)],
+            len: 0,
+        }
+    }
+
+    /// Returns the number of bytes required to hold a `SCM_RIGHTS` message of
+    /// `nfds` file descriptors.
+    #[inline]
+    pub fn space_for_rights(nfds: usize) -> usize {
+        unsafe { CMSG_SPACE((nfds * mem::size_of::<RawFd>()) as _) as _ }
+    }
+
+    /// Returns the number of bytes required to hold a `SCM_CREDENTIALS`
+    /// message.
+    #[inline]
+    pub fn space_for_credentials() -> usize {
+        unsafe { CMSG_SPACE(mem::size_of::<ucred>() as _) as _ }
+    }
+
+    /// Encode a `SCM_RIGHTS` message, which passes the file descriptors `fds`
+    /// to the receiving process.
+    ///
+    /// The file descriptors must be kept open until the message is sent.
+    pub fn push_rights(&mut self, fds: &[RawFd]) -> &mut Self {
+        let data =
+            unsafe { slice::from_raw_parts(fds.as_ptr() as *const u8, mem::size_of_val(fds)) };
+        self.push(SOL_SOCKET, SCM_RIGHTS, data);
+        self
+    }
+
+    /// Encode a `SCM_CREDENTIALS` message, which passes the credentials
+    /// `cred` to the receiving process.
+    pub fn push_credentials(&mut self, cred: Credentials) -> &mut Self {
+        let cred = ucred {
+            pid: cred.pid,
+            uid: cred.uid,
+            gid: cred.gid,
+        };
+        let data = unsafe {
+            slice::from_raw_parts(&cred as *const _ as *const u8, mem::size_of::<ucred>())
+        };
+        self.push(SOL_SOCKET, SCM_CREDENTIALS, data);
+        self
+    }
+
+    fn push(&mut self, level: c_int, ty: c_int, data: &[u8]) {
+        let offset = self.len;
+        let len = unsafe { CMSG_SPACE(data.len() as _) } as usize;
+        self.len += len;
+        if self.buf.len() * 8 < self.len {
+            self.buf.resize(self.len.div_ceil(8), 0);
+        }
+
+        unsafe {
+            let hdr = self.as_mut_ptr().add(offset);
+            ptr::write_bytes(hdr, 0, len);
+            ptr::write(
+                hdr as *mut cmsghdr,
+                cmsghdr {
+                    cmsg_len: CMSG_LEN(data.len() as _) as _,
+                    cmsg_level: level,
+                    cmsg_type: ty,
+                },
+            );
+            ptr::copy_nonoverlapping(data.as_ptr(), hdr.add(CMSG_LEN(0) as _), data.len());
+        }
+    }
+
+    /// Decode the first `len` bytes of control messages received by the
+    /// kernel, where `len` is the [`MsgHdr::control_len`] after the operation
+    /// is completed.
+    ///
+    /// The received file descriptors are returned as [`OwnedFd`]s. The buffer
+    /// is emptied afterwards and can be reused for receiving another message.
+    ///
+    /// # Safety
+    ///
+    /// The buffer must be filled by the kernel with a message received by
+    /// recvmsg, and not be modified since then. Otherwise the `SCM_RIGHTS`
+    /// message may carry file descriptors owned by someone else (e.g. the ones
+    /// encoded by [`push_rights`]), which would be closed twice.
+    ///
+    /// [`MsgHdr::control_len`]: method@crate::op::MsgHdr::control_len
+    /// [`push_rights`]: method@Self::push_rights
+    pub unsafe fn decode(&mut self, len: usize) -> Vec<ControlMessage> {
+        let len = len.min(self.capacity());
+        let hdr_len = unsafe { CMSG_LEN(0) } as usize;
+        let mut msgs = Vec::new();
+        let mut offset = 0;
+
+        while offset + mem::size_of::<cmsghdr>() <= len {
+            let hdr = unsafe { ptr::read(self.as_ptr().add(offset) as *const cmsghdr) };
+            let cmsg_len = hdr.cmsg_len as usize;
+            if cmsg_len < hdr_len || offset + cmsg_len > len {
+                break;
+            }
+            let data = unsafe {
+                slice::from_raw_parts(self.as_ptr().add(offset + hdr_len), cmsg_len - hdr_len)
+            };
+
+            msgs.push(match (hdr.cmsg_level, hdr.cmsg_type) {
+                (SOL_SOCKET, SCM_RIGHTS) => ControlMessage::ScmRights(
+                    data.chunks_exact(mem::size_of::<RawFd>())
+                        .map(|fd| unsafe {
+                            OwnedFd::from_raw_fd(RawFd::from_ne_bytes(fd.try_into().unwrap()))
+                        })
+                        .collect(),
+                ),
+                (SOL_SOCKET, SCM_CREDENTIALS) if data.len() >= mem::size_of::<ucred>() => {
+                    let cred = unsafe { ptr::read_unaligned(data.as_ptr() as *const ucred) };
+                    ControlMessage::ScmCredentials(Credentials {
+                        pid: cred.pid,
+                        uid: cred.uid,
+                        gid: cred.gid,
+                    })
+                }
+                (level, ty) => ControlMessage::Unknown {
+                    level,
+                    ty,
+                    data: data.to_vec(),
+                },
+            });
+            offset += unsafe { CMSG_SPACE((cmsg_len - hdr_len) as _) } as usize;
+        }
+
+        // Forget the decoded messages, not to take the ownership of the file
+        // descriptors twice.
+        for word in self.buf.iter_mut() {
+            *word = 0;
+        }
+        self.len = 0;
+        msgs
+    }
+
+    /// Returns the encoded control messages.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.as_ptr(), self.len) }
+    }
+
+    /// Returns the whole buffer to be filled with received control messages.
+    #[inline]
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        unsafe { slice::from_raw_parts_mut(self.as_mut_ptr(), self.capacity()) }
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.buf.len() * 8
+    }
+
+    #[inline]
+    fn as_ptr(&self) -> *const u8 {
+        self.buf.as_ptr() as _
+    }
+
+    #[inline]
+    fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.buf.as_mut_ptr() as _
+    }
+}
@@ -1,18 +1,63 @@
-use std::io;
+use std::{
+    any::Any,
+    io,
+    os::unix::io::{FromRawFd, OwnedFd},
+};
 
-use crate::{resultify, sys::io_uring_cqe};
+use crate::{
+    resultify,
+    sys::{
+        io_uring_cqe, IORING_CQE_BUFFER_SHIFT, IORING_CQE_F_BUFFER, IORING_CQE_F_BUF_MORE,
+        IORING_CQE_F_MORE, IORING_CQE_F_NOTIF,
+    },
+};
 
 /// CQE(Completion Queue Event), which represents a completed IO event.
 ///
 /// This is added by kernel to CQ(Completion Queue) for each SQE that is
 /// submitted. It contains the result of the operation submitted as part of the
 /// SQE.
-pub struct Cqe(io_uring_cqe);
+pub struct Cqe(
+    io_uring_cqe,
+    Option<[u64; 2]>,
+    Option<Box<dyn Any + Send>>,
+    // The id of the io_uring instance and the buffer group which the buffer
+    // is selected from.
+    Option<(u64, u16)>,
+);
 
 impl Cqe {
     #[inline]
-    pub(crate) fn new(cqe: &io_uring_cqe) -> Self {
-        Self(*cqe)
+    pub(crate) fn new(cqe: &io_uring_cqe, big: Option<[u64; 2]>) -> Self {
+        Self(*cqe, big, None, None)
+    }
+
+    /// Hand back the `user_data` of the tracked operation which produced this
+    /// event, along with the buffer owned by it if any.
+    #[inline]
+    pub(crate) fn with_buf(mut self, user_data: u64, buf: Option<Box<dyn Any + Send>>) -> Self {
+        self.0.user_data = user_data;
+        self.2 = buf;
+        self
+    }
+
+    /// Record that the buffer is selected from the group `bgid` of the
+    /// io_uring instance identified by `uring`.
+    #[inline]
+    pub(crate) fn with_group(mut self, uring: u64, bgid: u16) -> Self {
+        self.3 = Some((uring, bgid));
+        self
+    }
+
+    /// Returns the id of the provided buffer which is selected from the group
+    /// `bgid` of the io_uring instance identified by `uring`, if any.
+    #[inline]
+    pub(crate) fn buffer_id_in(&self, uring: u64, bgid: u16) -> Option<u16> {
+        if self.3 == Some((uring, bgid)) {
+            self.buffer_id()
+        } else {
+            None
+        }
     }
 
     #[inline]
@@ -24,4 +69,105 @@ impl Cqe {
     pub fn result(&self) -> io::Result<u32> {
         resultify(self.0.res)
     }
+
+    /// Returns the id of the provided buffer which is selected by the
+    /// operation, if any.
+    ///
+    /// See [`BufferGroup`] for details.
+    ///
+    /// [`BufferGroup`]: struct@crate::op::BufferGroup
+    #[inline]
+    pub fn buffer_id(&self) -> Option<u16> {
+        if self.0.flags & IORING_CQE_F_BUFFER != 0 {
+            Some((self.0.flags >> IORING_CQE_BUFFER_SHIFT) as _)
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if the selected buffer is consumed only partially, so
+    /// it stays in the buffer ring and will be used by the following events.
+    ///
+    /// It's only reported by the buffer ring registered with
+    /// [`BufRingFlags::INC`].
+    ///
+    /// [`BufRingFlags::INC`]: crate::BufRingFlags::INC
+    #[inline]
+    pub fn buffer_more(&self) -> bool {
+        self.0.flags & IORING_CQE_F_BUF_MORE != 0
+    }
+
+    /// Returns `true` if the SQE which produced this event will generate more
+    /// events (e.g. multishot operations). Once it returns `false`, the
+    /// operation is terminated and must be resubmitted to get more events.
+    #[inline]
+    pub fn has_more(&self) -> bool {
+        self.0.flags & IORING_CQE_F_MORE != 0
+    }
+
+    /// Returns `true` if it's the notification of a zero-copy send, which
+    /// tells that the kernel doesn't use the buffer anymore.
+    ///
+    /// See [`packup_send_zc_owned`] for details.
+    ///
+    /// [`packup_send_zc_owned`]: method@crate::Packer::packup_send_zc_owned
+    #[inline]
+    pub fn is_notification(&self) -> bool {
+        self.0.flags & IORING_CQE_F_NOTIF != 0
+    }
+
+    /// Takes the buffer owned by the operation which produced this event
+    /// (e.g. [`packup_read_owned`], the peer address of [`packup_accept`]),
+    /// whose type is `B`.
+    ///
+    /// `None` is returned if the operation doesn't own a buffer, the buffer
+    /// is already taken, or it's not of the type `B`.
+    ///
+    /// [`packup_read_owned`]: method@crate::Packer::packup_read_owned
+    /// [`packup_accept`]: method@crate::Packer::packup_accept
+    #[inline]
+    pub fn take_buf<B: Send + 'static>(&mut self) -> Option<B> {
+        match self.2.take()?.downcast::<B>() {
+            Ok(buf) => Some(*buf),
+            Err(buf) => {
+                self.2 = Some(buf);
+                None
+            }
+        }
+    }
+
+    /// Returns the extra 16 bytes of the event, if the CQ is set up with
+    /// [`Builder::big_cqe`]. Its meaning depends on the operation (e.g. the
+    /// driver specific result of [`packup_uring_cmd`]).
+    ///
+    /// [`Builder::big_cqe`]: method@crate::Builder::big_cqe
+    /// [`packup_uring_cmd`]: method@crate::Packer::packup_uring_cmd
+    #[inline]
+    pub fn big_cqe(&self) -> Option<&[u64; 2]> {
+        self.1.as_ref()
+    }
+
+    /// Converts the result into the [`OwnedFd`] which owns the new file
+    /// descriptor created by the operation (e.g. [`packup_fixed_fd_install`],
+    /// [`packup_openat`], [`packup_socket`], [`packup_accept`]).
+    ///
+    /// # Errors
+    ///
+    /// If the operation failed, then the error is returned.
+    ///
+    /// # Safety
+    ///
+    /// The event must be produced by the operation whose result is a new file
+    /// descriptor in the normal file table, which is not owned by anything
+    /// else. Especially, it must not be the operation that installs the file
+    /// into the fixed file table, whose result is an index.
+    ///
+    /// [`packup_fixed_fd_install`]: method@crate::Packer::packup_fixed_fd_install
+    /// [`packup_openat`]: method@crate::Packer::packup_openat
+    /// [`packup_socket`]: method@crate::Packer::packup_socket
+    /// [`packup_accept`]: method@crate::Packer::packup_accept
+    #[inline]
+    pub unsafe fn into_fd(self) -> io::Result<OwnedFd> {
+        Ok(OwnedFd::from_raw_fd(self.result()? as _))
+    }
 }
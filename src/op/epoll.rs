@@ -0,0 +1,78 @@
+use libc::{c_int, epoll_event};
+
+use super::EpollFlags;
+
+/// An operation to be performed on the interest list of an epoll instance by
+/// [`packup_epoll_ctl`].
+///
+/// [`packup_epoll_ctl`]: method@crate::Packer::packup_epoll_ctl
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EpollOp {
+    /// Add the file descriptor to the interest list.
+    Add,
+    /// Change the settings associated with the file descriptor.
+    Modify,
+    /// Remove the file descriptor from the interest list.
+    Delete,
+}
+
+impl From<EpollOp> for c_int {
+    fn from(op: EpollOp) -> Self {
+        match op {
+            EpollOp::Add => libc::EPOLL_CTL_ADD,
+            EpollOp::Modify => libc::EPOLL_CTL_MOD,
+            EpollOp::Delete => libc::EPOLL_CTL_DEL,
+        }
+    }
+}
+
+/// An owned storage of an epoll event.
+///
+/// It's used either as an input of [`packup_epoll_ctl`], which describes the
+/// events to be monitored, or as an output of [`packup_epoll_wait`], which
+/// will be filled by the kernel with the ready events.
+///
+/// [`packup_epoll_ctl`]: method@crate::Packer::packup_epoll_ctl
+/// [`packup_epoll_wait`]: method@crate::Packer::packup_epoll_wait
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct EpollEvent(epoll_event);
+
+impl EpollEvent {
+    /// Create a new [`EpollEvent`] with the `events` and the `data` which will
+    /// be returned when the file descriptor becomes ready.
+    pub fn new(events: EpollFlags, data: u64) -> Self {
+        Self(epoll_event {
+            events: events.bits(),
+            u64: data,
+        })
+    }
+
+    /// Create a new empty [`EpollEvent`].
+    pub fn empty() -> Self {
+        Self::new(EpollFlags::empty(), 0)
+    }
+
+    /// Returns the events.
+    #[inline]
+    pub fn events(&self) -> EpollFlags {
+        EpollFlags::from_bits_truncate(self.0.events)
+    }
+
+    /// Returns the user data.
+    #[inline]
+    pub fn data(&self) -> u64 {
+        self.0.u64
+    }
+
+    #[inline]
+    pub(crate) fn as_ptr(&self) -> *const epoll_event {
+        &self.0
+    }
+}
+
+impl Default for EpollEvent {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
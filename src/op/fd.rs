@@ -0,0 +1,21 @@
+use std::os::unix::io::RawFd;
+
+/// A file descriptor which is either a normal one or an index into the fixed
+/// file table.
+///
+/// See [`register_files`] for the fixed file table.
+///
+/// [`register_files`]: method@crate::Registrar::register_files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Fd {
+    /// A normal file descriptor.
+    Raw(RawFd),
+    /// An index into the fixed file table.
+    Fixed(u32),
+}
+
+impl From<RawFd> for Fd {
+    fn from(fd: RawFd) -> Self {
+        Self::Raw(fd)
+    }
+}
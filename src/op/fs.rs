@@ -0,0 +1,206 @@
+use std::{mem, time::Duration};
+
+use libc::{c_int, mode_t, open_how, statx, statx_timestamp};
+
+use super::{OFlag, ResolveFlags, StatxMask};
+
+/// How to open a file by [`packup_openat2`].
+///
+/// It's an extensible version of the `flags` and `mode` of openat(2), with
+/// the [`ResolveFlags`] which restrict how the path is resolved.
+///
+/// [`packup_openat2`]: method@crate::Packer::packup_openat2
+#[derive(Clone, Copy)]
+pub struct OpenHow(open_how);
+
+impl OpenHow {
+    /// Create a new [`OpenHow`] with the given `flags`.
+    pub fn new(flags: OFlag) -> Self {
+        let mut how: open_how = unsafe { mem::zeroed() };
+        how.flags = flags.bits() as _;
+        Self(how)
+    }
+
+    /// Set the file mode bits to be applied when a new file is created.
+    ///
+    /// It must be zero unless [`OFlag::CREAT`] or [`OFlag::TMPFILE`] is set.
+    pub fn mode(&mut self, mode: mode_t) -> &mut Self {
+        self.0.mode = mode as _;
+        self
+    }
+
+    /// Set the flags which restrict how the path is resolved.
+    pub fn resolve(&mut self, resolve: ResolveFlags) -> &mut Self {
+        self.0.resolve = resolve.bits();
+        self
+    }
+
+    #[inline]
+    pub(crate) fn as_ptr(&self) -> *const open_how {
+        &self.0
+    }
+}
+
+/// A caller-owned storage of the file status filled by [`packup_statx`].
+///
+/// Only the fields reported by [`mask`] are valid, the others are zero.
+///
+/// [`packup_statx`]: method@crate::Packer::packup_statx
+/// [`mask`]: method@Self::mask
+#[derive(Clone, Copy)]
+pub struct Statx(statx);
+
+impl Statx {
+    /// Create a new zeroed [`Statx`].
+    pub fn new() -> Self {
+        Self(unsafe { mem::zeroed() })
+    }
+
+    /// Returns the fields which are filled by the kernel.
+    #[inline]
+    pub fn mask(&self) -> StatxMask {
+        StatxMask::from_bits_truncate(self.0.stx_mask)
+    }
+
+    /// Returns the file type and mode.
+    #[inline]
+    pub fn mode(&self) -> mode_t {
+        self.0.stx_mode as _
+    }
+
+    /// Returns the number of hard links.
+    #[inline]
+    pub fn nlink(&self) -> u32 {
+        self.0.stx_nlink
+    }
+
+    /// Returns the user ID of the owner.
+    #[inline]
+    pub fn uid(&self) -> u32 {
+        self.0.stx_uid
+    }
+
+    /// Returns the group ID of the owner.
+    #[inline]
+    pub fn gid(&self) -> u32 {
+        self.0.stx_gid
+    }
+
+    /// Returns the inode number.
+    #[inline]
+    pub fn ino(&self) -> u64 {
+        self.0.stx_ino
+    }
+
+    /// Returns the total size in bytes.
+    #[inline]
+    pub fn size(&self) -> u64 {
+        self.0.stx_size
+    }
+
+    /// Returns the number of 512 byte blocks allocated.
+    #[inline]
+    pub fn blocks(&self) -> u64 {
+        self.0.stx_blocks
+    }
+
+    /// Returns the block size for filesystem I/O.
+    #[inline]
+    pub fn blksize(&self) -> u32 {
+        self.0.stx_blksize
+    }
+
+    /// Returns the time of the last access since the Unix epoch.
+    #[inline]
+    pub fn atime(&self) -> Duration {
+        to_duration(&self.0.stx_atime)
+    }
+
+    /// Returns the time of the creation since the Unix epoch.
+    #[inline]
+    pub fn btime(&self) -> Duration {
+        to_duration(&self.0.stx_btime)
+    }
+
+    /// Returns the time of the last status change since the Unix epoch.
+    #[inline]
+    pub fn ctime(&self) -> Duration {
+        to_duration(&self.0.stx_ctime)
+    }
+
+    /// Returns the time of the last modification since the Unix epoch.
+    #[inline]
+    pub fn mtime(&self) -> Duration {
+        to_duration(&self.0.stx_mtime)
+    }
+
+    /// Returns the raw `statx` structure for the fields without accessors.
+    #[inline]
+    pub fn as_raw(&self) -> &statx {
+        &self.0
+    }
+
+    #[inline]
+    pub(crate) fn as_mut_ptr(&mut self) -> *mut statx {
+        &mut self.0
+    }
+}
+
+impl Default for Statx {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[inline]
+fn to_duration(ts: &statx_timestamp) -> Duration {
+    Duration::new(ts.tv_sec as _, ts.tv_nsec)
+}
+
+/// An advice about the expected access pattern of file data or memory.
+///
+/// The advice is used by [`packup_fadvise`] and [`packup_madvise`]. Some of
+/// them are only valid for one of those, see posix_fadvise(2) and madvise(2).
+///
+/// [`packup_fadvise`]: method@crate::Packer::packup_fadvise
+/// [`packup_madvise`]: method@crate::Packer::packup_madvise
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Advice(c_int);
+
+impl Advice {
+    /// No special treatment.
+    pub const NORMAL: Self = Self(libc::POSIX_FADV_NORMAL);
+    /// Expect accesses in random order.
+    pub const RANDOM: Self = Self(libc::POSIX_FADV_RANDOM);
+    /// Expect accesses in sequential order.
+    pub const SEQUENTIAL: Self = Self(libc::POSIX_FADV_SEQUENTIAL);
+    /// Expect accesses in the near future.
+    pub const WILLNEED: Self = Self(libc::POSIX_FADV_WILLNEED);
+    /// Do not expect accesses in the near future.
+    pub const DONTNEED: Self = Self(libc::POSIX_FADV_DONTNEED);
+    /// Expect the data to be accessed only once. Only for file data.
+    pub const NOREUSE: Self = Self(libc::POSIX_FADV_NOREUSE);
+    /// The pages in the range can be freed lazily. Only for memory.
+    pub const FREE: Self = Self(libc::MADV_FREE);
+    /// Enable transparent huge pages for the range. Only for memory.
+    pub const HUGEPAGE: Self = Self(libc::MADV_HUGEPAGE);
+    /// Disable transparent huge pages for the range. Only for memory.
+    pub const NOHUGEPAGE: Self = Self(libc::MADV_NOHUGEPAGE);
+    /// Deactivate the pages in the range, making them more likely to be
+    /// reclaimed. Only for memory.
+    pub const COLD: Self = Self(libc::MADV_COLD);
+    /// Reclaim the pages in the range. Only for memory.
+    pub const PAGEOUT: Self = Self(libc::MADV_PAGEOUT);
+}
+
+impl From<c_int> for Advice {
+    fn from(advice: c_int) -> Self {
+        Self(advice)
+    }
+}
+
+impl From<Advice> for c_int {
+    fn from(advice: Advice) -> Self {
+        advice.0
+    }
+}
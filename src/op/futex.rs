@@ -0,0 +1,73 @@
+use std::{
+    mem,
+    ops::Deref,
+    sync::{atomic::AtomicU32, Arc},
+};
+
+use libc::futex_waitv;
+
+use super::FutexFlags;
+
+/// A 32-bit futex word, which is the target of futex operations such as
+/// [`packup_futex_wait`] and [`packup_futex_wake`].
+///
+/// It dereferences to the underlying [`AtomicU32`], which is used to
+/// manipulate the value of the futex word.
+///
+/// [`packup_futex_wait`]: method@crate::Packer::packup_futex_wait
+/// [`packup_futex_wake`]: method@crate::Packer::packup_futex_wake
+#[derive(Debug, Default)]
+#[repr(transparent)]
+pub struct Futex(AtomicU32);
+
+impl Futex {
+    /// The bitmask which matches any waiter.
+    pub const MATCH_ANY: u32 = libc::FUTEX_BITSET_MATCH_ANY as _;
+
+    /// Create a new [`Futex`] with the initial value `val`.
+    pub const fn new(val: u32) -> Self {
+        Self(AtomicU32::new(val))
+    }
+
+    #[inline]
+    pub(crate) fn as_ptr(&self) -> *const AtomicU32 {
+        &self.0
+    }
+}
+
+impl Deref for Futex {
+    type Target = AtomicU32;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// One of the futexes to wait on by [`packup_futex_waitv`].
+///
+/// [`packup_futex_waitv`]: method@crate::Packer::packup_futex_waitv
+pub struct FutexWaitV {
+    waitv: futex_waitv,
+    _futex: Arc<Futex>,
+}
+
+impl FutexWaitV {
+    /// Create a new [`FutexWaitV`] which waits on the `futex` as long as its
+    /// value is `expected`.
+    pub fn new(futex: Arc<Futex>, expected: u32, flags: FutexFlags) -> Self {
+        let mut waitv: futex_waitv = unsafe { mem::zeroed() };
+        waitv.val = expected as _;
+        waitv.uaddr = futex.as_ptr() as _;
+        waitv.flags = flags.bits() | libc::FUTEX2_SIZE_U32 as u32;
+        Self {
+            waitv,
+            _futex: futex,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn as_raw(&self) -> futex_waitv {
+        self.waitv
+    }
+}
@@ -3,9 +3,55 @@
 pub(super) mod cqe;
 pub(super) mod sqe;
 
+mod addr;
+pub use addr::SockAddrStorage;
+
+mod buf;
+pub(crate) use buf::Slab;
+pub use buf::{BufSelect, BufferGroup};
+
+mod cmsg;
+pub use cmsg::{CmsgBuf, ControlMessage, Credentials};
+
+mod epoll;
+pub use epoll::{EpollEvent, EpollOp};
+
+mod fd;
+pub use fd::Fd;
+
+mod fs;
+pub use fs::{Advice, OpenHow, Statx};
+
+mod futex;
+pub use futex::{Futex, FutexWaitV};
+
+mod msg;
+pub use msg::{MsgHdr, RecvMsgOut};
+
+mod operation;
+pub use operation::{
+    Accept, Bind, Close, Connect, EpollCtl, FGetXattr, FSetXattr, Fadvise, Fallocate, Fsync,
+    Ftruncate, GetSockOpt, GetXattr, LinkAt, Listen, MkdirAt, Nop, OpenAt, OpenAt2, Operation,
+    PollAdd, Read, Readv, Recv, RenameAt, Send, SetSockOpt, SetXattr, Shutdown, Socket, Splice,
+    SymlinkAt, SyncFileRange, Tee, UnlinkAt, UringCmd, Write, Writev,
+};
+
+mod owned;
+pub use owned::{IoBuf, IoBufMut};
+pub(crate) use owned::{IoVecs, Registry, INTERNAL_USER_DATA, OWNED_USER_DATA};
+
+mod socket;
+pub use socket::{Domain, Protocol, SockType};
+
+mod time;
+pub use time::{TickCounter, Timespec};
+
+mod wait;
+pub use wait::{ChildStatus, WaitId, WaitInfo};
+
 use bitflags::bitflags;
 
-use crate::sys::IORING_FSYNC_DATASYNC;
+use crate::sys::{IORING_FIXED_FD_NO_CLOEXEC, IORING_FSYNC_DATASYNC};
 
 bitflags! {
     /// Synchronized I/O file or data integrity completion.
@@ -21,6 +67,70 @@ bitflags! {
     }
 }
 
+bitflags! {
+    /// The operation to be performed on the byte range of a file by
+    /// [`packup_fallocate`].
+    ///
+    /// [`packup_fallocate`]: method@crate::Packer::packup_fallocate
+    pub struct FallocateMode: u32 {
+        /// Allocate and initialize to zero the disk space, extending the file
+        /// size if the range goes beyond the end of the file.
+        const ALLOCATE = 0;
+        /// Don't change the file size even if the range goes beyond the end of
+        /// the file.
+        const KEEP_SIZE = libc::FALLOC_FL_KEEP_SIZE as _;
+        /// Deallocate the space, creating a hole. It must be ORed with
+        /// [`KEEP_SIZE`].
+        ///
+        /// [`KEEP_SIZE`]: Self::KEEP_SIZE
+        const PUNCH_HOLE = libc::FALLOC_FL_PUNCH_HOLE as _;
+        /// Remove the range from the file without leaving a hole.
+        const COLLAPSE_RANGE = libc::FALLOC_FL_COLLAPSE_RANGE as _;
+        /// Zero the range, allocating the space if needed.
+        const ZERO_RANGE = libc::FALLOC_FL_ZERO_RANGE as _;
+        /// Insert a hole of the range without overwriting any existing data.
+        const INSERT_RANGE = libc::FALLOC_FL_INSERT_RANGE as _;
+        /// Unshare the shared data blocks within the range.
+        const UNSHARE_RANGE = libc::FALLOC_FL_UNSHARE_RANGE as _;
+    }
+}
+
+bitflags! {
+    /// Flags which control how the byte range of a file is synchronized by
+    /// [`packup_sync_file_range`].
+    ///
+    /// [`packup_sync_file_range`]: method@crate::Packer::packup_sync_file_range
+    pub struct SyncRangeFlags: u32 {
+        /// Wait upon write-out of all pages in the range that have already
+        /// been submitted to the device driver for write-out.
+        const WAIT_BEFORE = libc::SYNC_FILE_RANGE_WAIT_BEFORE;
+        /// Initiate write-out of all dirty pages in the range which are not
+        /// presently submitted write-out.
+        const WRITE = libc::SYNC_FILE_RANGE_WRITE;
+        /// Wait upon write-out of all pages in the range after performing any
+        /// write.
+        const WAIT_AFTER = libc::SYNC_FILE_RANGE_WAIT_AFTER;
+    }
+}
+
+bitflags! {
+    /// Flags which modify the behavior of moving data between file
+    /// descriptors by [`packup_splice`] and [`packup_tee`].
+    ///
+    /// [`packup_splice`]: method@crate::Packer::packup_splice
+    /// [`packup_tee`]: method@crate::Packer::packup_tee
+    pub struct SpliceFlags: u32 {
+        /// Attempt to move pages instead of copying.
+        const MOVE = libc::SPLICE_F_MOVE;
+        /// Do not block on I/O.
+        const NONBLOCK = libc::SPLICE_F_NONBLOCK;
+        /// More data will be coming in a subsequent splice.
+        const MORE = libc::SPLICE_F_MORE;
+        /// Unused for splice, see vmsplice(2).
+        const GIFT = libc::SPLICE_F_GIFT;
+    }
+}
+
 bitflags! {
     /// The bit mask specifying the events the application is interested in.
     pub struct PollEvent: u32 {
@@ -38,3 +148,287 @@ bitflags! {
         const NVAL = libc::POLLNVAL as _;
     }
 }
+
+bitflags! {
+    /// The bit mask specifying the events of an epoll instance.
+    pub struct EpollFlags: u32 {
+        /// The file is available for read operations.
+        const IN = libc::EPOLLIN as _;
+        /// There is an exceptional condition on the file.
+        const PRI = libc::EPOLLPRI as _;
+        /// The file is available for write operations.
+        const OUT = libc::EPOLLOUT as _;
+        /// Error condition.
+        const ERR = libc::EPOLLERR as _;
+        /// Hung up.
+        const HUP = libc::EPOLLHUP as _;
+        /// Stream socket peer closed connection, or shut down writing half of
+        /// connection.
+        const RDHUP = libc::EPOLLRDHUP as _;
+        /// Set an exclusive wakeup mode for the epoll file descriptor.
+        const EXCLUSIVE = libc::EPOLLEXCLUSIVE as _;
+        /// Ensure that the system does not enter suspend or hibernate while
+        /// the event is pending or being processed.
+        const WAKEUP = libc::EPOLLWAKEUP as _;
+        /// Disable the file descriptor after the event is reported once.
+        const ONESHOT = libc::EPOLLONESHOT as _;
+        /// Request edge-triggered notification.
+        const ET = libc::EPOLLET as _;
+    }
+}
+
+bitflags! {
+    /// Flags which will be set on the file descriptor of a new socket.
+    pub struct SockFlags: u32 {
+        /// Set the `O_NONBLOCK` file status flag on the new open file
+        /// description.
+        const NONBLOCK = libc::SOCK_NONBLOCK as _;
+        /// Set the close-on-exec (`FD_CLOEXEC`) flag on the new file
+        /// descriptor.
+        const CLOEXEC = libc::SOCK_CLOEXEC as _;
+    }
+}
+
+bitflags! {
+    /// Flags which modify the behavior of sending or receiving a message. When
+    /// a message is received, some of them are also used to report the
+    /// condition of the message.
+    pub struct MsgFlags: u32 {
+        /// Requests receipt of out-of-band data, or sends out-of-band data.
+        const OOB = libc::MSG_OOB as _;
+        /// Return data from the beginning of the receive queue without
+        /// removing it from the queue.
+        const PEEK = libc::MSG_PEEK as _;
+        /// Don't use a gateway to send out the packet.
+        const DONTROUTE = libc::MSG_DONTROUTE as _;
+        /// Indicates that the control data was discarded due to lack of space
+        /// in the buffer for ancillary data.
+        const CTRUNC = libc::MSG_CTRUNC as _;
+        /// Return the real length of the packet or datagram, even when it was
+        /// longer than the passed buffer. When it's reported, indicates that
+        /// the trailing portion of a datagram was discarded.
+        const TRUNC = libc::MSG_TRUNC as _;
+        /// Enables nonblocking operation.
+        const DONTWAIT = libc::MSG_DONTWAIT as _;
+        /// Terminates a record.
+        const EOR = libc::MSG_EOR as _;
+        /// Block until the full request is satisfied.
+        const WAITALL = libc::MSG_WAITALL as _;
+        /// Tell the link layer that forward progress happened.
+        const CONFIRM = libc::MSG_CONFIRM as _;
+        /// Receive queued errors from the socket error queue.
+        const ERRQUEUE = libc::MSG_ERRQUEUE as _;
+        /// Don't generate a `SIGPIPE` signal if the peer on a stream-oriented
+        /// socket has closed the connection.
+        const NOSIGNAL = libc::MSG_NOSIGNAL as _;
+        /// The caller has more data to send.
+        const MORE = libc::MSG_MORE as _;
+        /// Set the close-on-exec flag for the file descriptors received via
+        /// `SCM_RIGHTS`.
+        const CMSG_CLOEXEC = libc::MSG_CMSG_CLOEXEC as _;
+    }
+}
+
+bitflags! {
+    /// File access modes and file status flags used when opening a file.
+    pub struct OFlag: u32 {
+        /// Open the file read-only.
+        const RDONLY = libc::O_RDONLY as _;
+        /// Open the file write-only.
+        const WRONLY = libc::O_WRONLY as _;
+        /// Open the file read/write.
+        const RDWR = libc::O_RDWR as _;
+        /// Create the file if it does not exist.
+        const CREAT = libc::O_CREAT as _;
+        /// Ensure that the call creates the file. Used with [`CREAT`], fails
+        /// with `EEXIST` if the file already exists.
+        ///
+        /// [`CREAT`]: Self::CREAT
+        const EXCL = libc::O_EXCL as _;
+        /// Don't make the terminal device the controlling terminal.
+        const NOCTTY = libc::O_NOCTTY as _;
+        /// Truncate the file to length 0 if it's a regular file opened for
+        /// writing.
+        const TRUNC = libc::O_TRUNC as _;
+        /// Open the file in append mode.
+        const APPEND = libc::O_APPEND as _;
+        /// Open the file in nonblocking mode.
+        const NONBLOCK = libc::O_NONBLOCK as _;
+        /// Write operations complete according to the requirements of
+        /// synchronized I/O data integrity completion.
+        const DSYNC = libc::O_DSYNC as _;
+        /// Write operations complete according to the requirements of
+        /// synchronized I/O file integrity completion.
+        const SYNC = libc::O_SYNC as _;
+        /// Try to minimize cache effects of the I/O to and from the file.
+        const DIRECT = libc::O_DIRECT as _;
+        /// Fail if the path is not a directory.
+        const DIRECTORY = libc::O_DIRECTORY as _;
+        /// Fail if the trailing component of the path is a symbolic link.
+        const NOFOLLOW = libc::O_NOFOLLOW as _;
+        /// Do not update the file last access time.
+        const NOATIME = libc::O_NOATIME as _;
+        /// Set the close-on-exec (`FD_CLOEXEC`) flag on the new file
+        /// descriptor.
+        const CLOEXEC = libc::O_CLOEXEC as _;
+        /// Obtain a file descriptor that only indicates a location in the
+        /// filesystem tree.
+        const PATH = libc::O_PATH as _;
+        /// Create an unnamed temporary regular file in the given directory.
+        const TMPFILE = libc::O_TMPFILE as _;
+    }
+}
+
+bitflags! {
+    /// Flags which restrict how the path is resolved by
+    /// [`packup_openat2`].
+    ///
+    /// [`packup_openat2`]: method@crate::Packer::packup_openat2
+    pub struct ResolveFlags: u64 {
+        /// Disallow traversal of mount points during path resolution.
+        const NO_XDEV = libc::RESOLVE_NO_XDEV;
+        /// Disallow all magic-link resolution during path resolution.
+        const NO_MAGICLINKS = libc::RESOLVE_NO_MAGICLINKS;
+        /// Disallow resolution of symbolic links during path resolution.
+        const NO_SYMLINKS = libc::RESOLVE_NO_SYMLINKS;
+        /// Do not permit the path resolution to succeed if any component of
+        /// the resolution is not a descendant of the directory.
+        const BENEATH = libc::RESOLVE_BENEATH;
+        /// Treat the directory as the root directory while resolving the path.
+        const IN_ROOT = libc::RESOLVE_IN_ROOT;
+        /// Make the open fail with `EAGAIN` unless all path components are
+        /// already present in the kernel's lookup cache.
+        const CACHED = libc::RESOLVE_CACHED;
+    }
+}
+
+bitflags! {
+    /// Flags which control how the path is looked up by the `*at` family of
+    /// operations.
+    pub struct AtFlags: u32 {
+        /// Operate on the directory file descriptor itself if the path is an
+        /// empty string.
+        const EMPTY_PATH = libc::AT_EMPTY_PATH as _;
+        /// Don't dereference the path if it's a symbolic link.
+        const SYMLINK_NOFOLLOW = libc::AT_SYMLINK_NOFOLLOW as _;
+        /// Dereference the path if it's a symbolic link.
+        const SYMLINK_FOLLOW = libc::AT_SYMLINK_FOLLOW as _;
+        /// Don't automount the terminal component of the path.
+        const NO_AUTOMOUNT = libc::AT_NO_AUTOMOUNT as _;
+        /// Remove the directory instead of the file.
+        const REMOVEDIR = libc::AT_REMOVEDIR as _;
+        /// Force the attributes to be synchronized with the server on a
+        /// network filesystem.
+        const STATX_FORCE_SYNC = libc::AT_STATX_FORCE_SYNC as _;
+        /// Don't synchronize anything, take whatever is cached.
+        const STATX_DONT_SYNC = libc::AT_STATX_DONT_SYNC as _;
+    }
+}
+
+bitflags! {
+    /// Flags which control how the file is renamed by [`packup_renameat`].
+    ///
+    /// [`packup_renameat`]: method@crate::Packer::packup_renameat
+    pub struct RenameFlags: u32 {
+        /// Don't overwrite the new path if it exists.
+        const NOREPLACE = libc::RENAME_NOREPLACE;
+        /// Atomically exchange the old path and the new path.
+        const EXCHANGE = libc::RENAME_EXCHANGE;
+        /// Create a whiteout object at the source of the rename at the same
+        /// time as performing the rename.
+        const WHITEOUT = libc::RENAME_WHITEOUT;
+    }
+}
+
+bitflags! {
+    /// The bit mask specifying the fields of [`Statx`] the application is
+    /// interested in.
+    pub struct StatxMask: u32 {
+        /// Want the file type in `stx_mode`.
+        const TYPE = libc::STATX_TYPE;
+        /// Want the file permissions in `stx_mode`.
+        const MODE = libc::STATX_MODE;
+        /// Want `stx_nlink`.
+        const NLINK = libc::STATX_NLINK;
+        /// Want `stx_uid`.
+        const UID = libc::STATX_UID;
+        /// Want `stx_gid`.
+        const GID = libc::STATX_GID;
+        /// Want `stx_atime`.
+        const ATIME = libc::STATX_ATIME;
+        /// Want `stx_mtime`.
+        const MTIME = libc::STATX_MTIME;
+        /// Want `stx_ctime`.
+        const CTIME = libc::STATX_CTIME;
+        /// Want `stx_ino`.
+        const INO = libc::STATX_INO;
+        /// Want `stx_size`.
+        const SIZE = libc::STATX_SIZE;
+        /// Want `stx_blocks`.
+        const BLOCKS = libc::STATX_BLOCKS;
+        /// All of the above, which is the same as what stat(2) returns.
+        const BASIC_STATS = libc::STATX_BASIC_STATS;
+        /// Want `stx_btime`.
+        const BTIME = libc::STATX_BTIME;
+        /// Want `stx_mnt_id`.
+        const MNT_ID = libc::STATX_MNT_ID;
+        /// Want the direct I/O alignment information.
+        const DIOALIGN = libc::STATX_DIOALIGN;
+    }
+}
+
+bitflags! {
+    /// Flags which control how an extended attribute is set by
+    /// [`packup_setxattr`] and [`packup_fsetxattr`].
+    ///
+    /// [`packup_setxattr`]: method@crate::Packer::packup_setxattr
+    /// [`packup_fsetxattr`]: method@crate::Packer::packup_fsetxattr
+    pub struct XattrFlags: u32 {
+        /// Fail if the named attribute already exists.
+        const CREATE = libc::XATTR_CREATE as _;
+        /// Fail if the named attribute does not exist.
+        const REPLACE = libc::XATTR_REPLACE as _;
+    }
+}
+
+bitflags! {
+    /// Flags which modify the behavior of a futex operation.
+    pub struct FutexFlags: u32 {
+        /// The futex is private to the process, which allows the kernel to
+        /// skip some of the bookkeeping needed for the shared one.
+        const PRIVATE = libc::FUTEX2_PRIVATE as _;
+    }
+}
+
+bitflags! {
+    /// Flags which specify the child state changes to wait for by
+    /// [`packup_waitid`].
+    ///
+    /// [`packup_waitid`]: method@crate::Packer::packup_waitid
+    pub struct WaitOptions: u32 {
+        /// Wait for children that have terminated.
+        const EXITED = libc::WEXITED as _;
+        /// Wait for children that have been stopped by delivery of a signal.
+        const STOPPED = libc::WSTOPPED as _;
+        /// Wait for (previously stopped) children that have been resumed by
+        /// delivery of `SIGCONT`.
+        const CONTINUED = libc::WCONTINUED as _;
+        /// Return immediately if no child has changed the state yet.
+        const NOHANG = libc::WNOHANG as _;
+        /// Leave the child in a waitable state, so that a later wait can be
+        /// used to retrieve the child status information again.
+        const NOWAIT = libc::WNOWAIT as _;
+    }
+}
+
+bitflags! {
+    /// Flags which will be set on the file descriptor installed by
+    /// [`packup_fixed_fd_install`].
+    ///
+    /// [`packup_fixed_fd_install`]: method@crate::Packer::packup_fixed_fd_install
+    pub struct InstallFlags: u32 {
+        /// Don't set the close-on-exec (`FD_CLOEXEC`) flag, which is set by
+        /// default.
+        const NO_CLOEXEC = IORING_FIXED_FD_NO_CLOEXEC;
+    }
+}
@@ -0,0 +1,264 @@
+use std::{
+    io::{IoSlice, IoSliceMut},
+    marker::PhantomData,
+    mem, ptr,
+};
+
+use libc::{msghdr, sockaddr_storage, socklen_t};
+
+use crate::sys::io_uring_recvmsg_out;
+
+use super::{CmsgBuf, MsgFlags, SockAddrStorage};
+
+/// A message header used by [`packup_sendmsg`] and [`packup_recvmsg`].
+///
+/// It's a safe builder of `struct msghdr`, which describes the peer address,
+/// the scatter/gather buffers and the ancillary data (control messages) of the
+/// message. All of them are borrowed for the lifetime `'a`, and the kernel
+/// accesses both the header and them until the operation is completed, so
+/// the [`MsgHdr`] must be kept alive until then.
+///
+/// When a [`packup_recvmsg`] is completed, the kernel updates the length of
+/// the address and the ancillary data and the flags of the received message,
+/// which can be inspected by [`name_len`], [`control_len`] and [`msg_flags`].
+///
+/// [`packup_sendmsg`]: method@crate::Packer::packup_sendmsg
+/// [`packup_recvmsg`]: method@crate::Packer::packup_recvmsg
+/// [`name_len`]: method@Self::name_len
+/// [`control_len`]: method@Self::control_len
+/// [`msg_flags`]: method@Self::msg_flags
+pub struct MsgHdr<'a> {
+    hdr: msghdr,
+    _marker: PhantomData<&'a mut [u8]>,
+}
+
+impl<'a> MsgHdr<'a> {
+    /// Create a new empty [`MsgHdr`].
+    pub fn new() -> Self {
+        Self {
+            hdr: unsafe { mem::zeroed() },
+            _marker: PhantomData,
+        }
+    }
+
+    /// Create a new [`MsgHdr`] for [`packup_recvmsg_multi`], which reserves
+    /// `name_len` bytes for the source address and `control_len` bytes for
+    /// the ancillary data in each selected buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name_len` is larger than the size of `sockaddr_storage`,
+    /// which can hold any socket address.
+    ///
+    /// [`packup_recvmsg_multi`]: method@crate::Packer::packup_recvmsg_multi
+    pub fn with_reserved(name_len: socklen_t, control_len: usize) -> Self {
+        assert!(
+            name_len as usize <= mem::size_of::<sockaddr_storage>(),
+            "name_len is larger than any socket address"
+        );
+        let mut msg = Self::new();
+        msg.hdr.msg_namelen = name_len;
+        msg.hdr.msg_controllen = control_len as _;
+        msg
+    }
+
+    /// Set the destination address of the message to be sent.
+    ///
+    /// It's used on an unconnected socket.
+    #[inline]
+    pub fn name(&mut self, addr: &'a SockAddrStorage) -> &mut Self {
+        self.hdr.msg_name = addr.as_ptr() as _;
+        self.hdr.msg_namelen = addr.len();
+        self
+    }
+
+    /// Set the buffer which will be filled with the source address of the
+    /// message to be received.
+    #[inline]
+    pub fn name_mut(&mut self, addr: &'a mut SockAddrStorage) -> &mut Self {
+        self.hdr.msg_namelen = addr.len();
+        self.hdr.msg_name = addr.as_mut_ptr() as _;
+        self
+    }
+
+    /// Set the slice of buffers to be sent.
+    #[inline]
+    pub fn iovecs(&mut self, bufs: &'a [IoSlice<'a>]) -> &mut Self {
+        self.hdr.msg_iov = bufs.as_ptr() as _;
+        self.hdr.msg_iovlen = bufs.len() as _;
+        self
+    }
+
+    /// Set the slice of buffers which will be filled with the received
+    /// message.
+    #[inline]
+    pub fn iovecs_mut(&mut self, bufs: &'a mut [IoSliceMut<'a>]) -> &mut Self {
+        self.hdr.msg_iov = bufs.as_mut_ptr() as _;
+        self.hdr.msg_iovlen = bufs.len() as _;
+        self
+    }
+
+    /// Set the ancillary data to be sent.
+    ///
+    /// See cmsg(3) for the format of the `buf`.
+    #[inline]
+    pub fn control(&mut self, buf: &'a [u8]) -> &mut Self {
+        self.hdr.msg_control = buf.as_ptr() as _;
+        self.hdr.msg_controllen = buf.len() as _;
+        self
+    }
+
+    /// Set the buffer which will be filled with the received ancillary data.
+    #[inline]
+    pub fn control_mut(&mut self, buf: &'a mut [u8]) -> &mut Self {
+        self.hdr.msg_control = buf.as_mut_ptr() as _;
+        self.hdr.msg_controllen = buf.len() as _;
+        self
+    }
+
+    /// Set the control messages encoded in the `cmsgs` to be sent.
+    #[inline]
+    pub fn cmsgs(&mut self, cmsgs: &'a CmsgBuf) -> &mut Self {
+        self.control(cmsgs.as_bytes())
+    }
+
+    /// Set the `cmsgs` which will be filled with the received control
+    /// messages. They can be decoded by [`CmsgBuf::decode`] with the
+    /// [`control_len`] once the operation is completed.
+    ///
+    /// [`CmsgBuf::decode`]: method@crate::op::CmsgBuf::decode
+    /// [`control_len`]: method@Self::control_len
+    #[inline]
+    pub fn cmsgs_mut(&mut self, cmsgs: &'a mut CmsgBuf) -> &mut Self {
+        self.control_mut(cmsgs.as_bytes_mut())
+    }
+
+    /// Returns the length of the address. After the message is received, it's
+    /// the length of the source address.
+    #[inline]
+    pub fn name_len(&self) -> socklen_t {
+        self.hdr.msg_namelen
+    }
+
+    /// Returns the length of the ancillary data. After the message is
+    /// received, it's the length of the received ancillary data.
+    #[inline]
+    pub fn control_len(&self) -> usize {
+        self.hdr.msg_controllen as _
+    }
+
+    /// Returns the flags of the received message (e.g. [`MsgFlags::TRUNC`],
+    /// [`MsgFlags::CTRUNC`]).
+    #[inline]
+    pub fn msg_flags(&self) -> MsgFlags {
+        MsgFlags::from_bits_truncate(self.hdr.msg_flags as _)
+    }
+
+    #[inline]
+    pub(crate) fn as_ptr(&self) -> *const msghdr {
+        &self.hdr
+    }
+
+    #[inline]
+    pub(crate) fn as_mut_ptr(&mut self) -> *mut msghdr {
+        &mut self.hdr
+    }
+}
+
+impl Default for MsgHdr<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A message received by [`packup_recvmsg_multi`], which is parsed from the
+/// selected buffer.
+///
+/// The kernel lays out the buffer as a header followed by the source address,
+/// the ancillary data and the payload, where the space of the address and the
+/// ancillary data is reserved by the [`MsgHdr`] passed to the operation.
+///
+/// [`packup_recvmsg_multi`]: method@crate::Packer::packup_recvmsg_multi
+pub struct RecvMsgOut<'a> {
+    header: io_uring_recvmsg_out,
+    name: &'a [u8],
+    control: &'a [u8],
+    payload: &'a [u8],
+}
+
+impl<'a> RecvMsgOut<'a> {
+    /// Parse the `buf` selected by the operation submitted with the `msg`.
+    ///
+    /// `None` is returned if the `buf` is too short to hold the reserved
+    /// space.
+    pub fn parse(buf: &'a [u8], msg: &MsgHdr<'_>) -> Option<Self> {
+        let header_len = mem::size_of::<io_uring_recvmsg_out>();
+        let name_end = header_len + msg.name_len() as usize;
+        let control_end = name_end + msg.control_len();
+        if buf.len() < control_end {
+            return None;
+        }
+
+        let header = unsafe { ptr::read_unaligned(buf.as_ptr() as *const io_uring_recvmsg_out) };
+        let name = &buf[header_len..name_end];
+        let control = &buf[name_end..control_end];
+        let payload = &buf[control_end..];
+        Some(Self {
+            name: &name[..name.len().min(header.namelen as _)],
+            control: &control[..control.len().min(header.controllen as _)],
+            payload: &payload[..payload.len().min(header.payloadlen as _)],
+            header,
+        })
+    }
+
+    /// Returns the source address of the message.
+    ///
+    /// It's truncated if the reserved space is not enough, which can be
+    /// checked by comparing [`name_len`] with the [`SockAddrStorage::len`].
+    ///
+    /// [`name_len`]: method@Self::name_len
+    /// [`SockAddrStorage::len`]: method@crate::op::SockAddrStorage::len
+    pub fn name(&self) -> SockAddrStorage {
+        // The header may come from anywhere, so the length is bounded.
+        let len = self.name.len().min(mem::size_of::<sockaddr_storage>());
+        unsafe { SockAddrStorage::from_raw(self.name.as_ptr() as _, len as _) }
+    }
+
+    /// Returns the real length of the source address.
+    #[inline]
+    pub fn name_len(&self) -> socklen_t {
+        self.header.namelen
+    }
+
+    /// Returns the received ancillary data.
+    ///
+    /// It's truncated if the reserved space is not enough, which is reported
+    /// by [`MsgFlags::CTRUNC`].
+    #[inline]
+    pub fn control(&self) -> &'a [u8] {
+        self.control
+    }
+
+    /// Returns the payload of the message in the buffer.
+    ///
+    /// It's truncated if the rest of the buffer is not enough, which can be
+    /// checked by comparing it with the [`payload_len`].
+    ///
+    /// [`payload_len`]: method@Self::payload_len
+    #[inline]
+    pub fn payload(&self) -> &'a [u8] {
+        self.payload
+    }
+
+    /// Returns the real length of the payload.
+    #[inline]
+    pub fn payload_len(&self) -> usize {
+        self.header.payloadlen as _
+    }
+
+    /// Returns the flags of the received message.
+    #[inline]
+    pub fn msg_flags(&self) -> MsgFlags {
+        MsgFlags::from_bits_truncate(self.header.flags)
+    }
+}
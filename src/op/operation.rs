@@ -0,0 +1,627 @@
+use std::{ffi::CString, net, os::unix::io::RawFd};
+
+use libc::{c_int, mode_t};
+
+use crate::Packer;
+
+use super::{
+    Advice, AtFlags, Domain, EpollEvent, EpollOp, FallocateMode, Fd, FsyncFlags, IoBuf, IoBufMut,
+    MsgFlags, OFlag, OpenHow, PollEvent, Protocol, RenameFlags, SockAddrStorage, SockFlags,
+    SockType, SpliceFlags, SyncRangeFlags, XattrFlags,
+};
+
+/// An operation which can be packed up into a SQE(Submission Queue Entry).
+///
+/// Each opcode has a struct implementing it (e.g. [`Read`]), whose fields are
+/// the arguments of the corresponding `packup_*` method of the [`Packer`]. So
+/// the operation can be built, stored, inspected or retried before it gets a
+/// SQE, and it's pushed to the SQ by [`Sq::push`].
+///
+/// The operations own all the data passed to the kernel (e.g. the buffer of
+/// [`Read`] or the path of [`OpenAt`]), which is kept by the io_uring
+/// instance until the operation is completed. So there's no struct for the
+/// opcodes borrowing the data the kernel accesses later, handing back more
+/// than one event for it, or filling a structure handed back by the [`Cqe`]:
+///
+/// - The fixed buffer I/O and the message I/O (e.g. `RECVMSG`), which borrow
+///   the buffers.
+/// - `STATX`, `EPOLL_WAIT` and `WAITID`, which fill the [`Statx`], the events
+///   and the [`WaitInfo`] handed back by the [`Cqe`].
+/// - The futex operations, which share the futex with other threads.
+/// - The buffer selection (e.g. `PROVIDE_BUFFERS`), which borrows the
+///   [`BufferGroup`] or [`BufRing`].
+/// - `MSG_RING`, which borrows the target ring.
+/// - The zero-copy sends, which use the buffer until the notification event.
+/// - The multishot operations (e.g. the periodic timeout), since the owned
+///   data is released at the first event.
+///
+/// They are packed up by the [`Packer`] or the [`Scope`] instead.
+///
+/// [`Sq::push`]: method@crate::Sq::push
+/// [`Cqe`]: struct@crate::Cqe
+/// [`Statx`]: struct@crate::op::Statx
+/// [`WaitInfo`]: struct@crate::op::WaitInfo
+/// [`BufferGroup`]: struct@crate::op::BufferGroup
+/// [`BufRing`]: struct@crate::BufRing
+/// [`Scope`]: struct@crate::Scope
+pub trait Operation {
+    /// Pack up the operation by the `packer`, which takes over the data
+    /// owned by it.
+    fn prepare(self, packer: &mut Packer<'_>)
+    where
+        Self: Sized;
+
+    /// Like [`prepare`], but the operation is boxed, so that a
+    /// `Box<dyn Operation>` can be pushed. It's usually implemented as
+    /// `(*self).prepare(packer)`.
+    ///
+    /// [`prepare`]: method@Self::prepare
+    fn prepare_boxed(self: Box<Self>, packer: &mut Packer<'_>);
+}
+
+impl<T: Operation + ?Sized> Operation for Box<T> {
+    #[inline]
+    fn prepare(self, packer: &mut Packer<'_>) {
+        T::prepare_boxed(self, packer)
+    }
+
+    #[inline]
+    fn prepare_boxed(self: Box<Self>, packer: &mut Packer<'_>) {
+        (*self).prepare(packer)
+    }
+}
+
+// Define the struct of the operation whose fields are public, with the `new`
+// constructor taking all of them in order. The operation is packed up by the
+// `prepare` expression, where `op` is the operation and `packer` is the
+// packer.
+macro_rules! operation {
+    (
+        $(#[$meta:meta])*
+        pub struct $name:ident $(<$($param:ident: $bound:ident),+>)? {
+            $($field:ident: $ty:ty,)*
+        }
+        |$op:ident, $packer:ident| $prepare:expr
+    ) => {
+        $(#[$meta])*
+        pub struct $name $(<$($param: $bound),+>)? {
+            $(pub $field: $ty,)*
+        }
+
+        impl $(<$($param: $bound),+>)? $name $(<$($param),+>)? {
+            #[doc = concat!("Create a new [`", stringify!($name), "`] operation.")]
+            #[allow(clippy::too_many_arguments)]
+            #[inline]
+            pub fn new($($field: $ty),*) -> Self {
+                Self { $($field),* }
+            }
+        }
+
+        impl $(<$($param: $bound),+>)? Operation for $name $(<$($param),+>)? {
+            #[inline]
+            fn prepare(self, $packer: &mut Packer<'_>) {
+                let $op = self;
+                $prepare
+            }
+
+            #[inline]
+            fn prepare_boxed(self: Box<Self>, packer: &mut Packer<'_>) {
+                (*self).prepare(packer)
+            }
+        }
+    };
+}
+
+operation! {
+    /// The operation that does not perform any I/O, see [`packup_nop`].
+    ///
+    /// [`packup_nop`]: method@crate::Packer::packup_nop
+    #[derive(Debug, Default)]
+    pub struct Nop {}
+    |_op, packer| packer.packup_nop()
+}
+
+operation! {
+    /// The operation that synchronizes the file with the storage device, see
+    /// [`packup_fsync`].
+    ///
+    /// [`packup_fsync`]: method@crate::Packer::packup_fsync
+    #[derive(Debug)]
+    pub struct Fsync {
+        fd: RawFd,
+        flags: FsyncFlags,
+    }
+    |op, packer| packer.packup_fsync(op.fd, op.flags)
+}
+
+operation! {
+    /// The operation that polls the file descriptor, see [`packup_poll_add`].
+    ///
+    /// [`packup_poll_add`]: method@crate::Packer::packup_poll_add
+    #[derive(Debug)]
+    pub struct PollAdd {
+        fd: RawFd,
+        events: PollEvent,
+    }
+    |op, packer| packer.packup_poll_add(op.fd, op.events)
+}
+
+operation! {
+    /// The operation that synchronizes the byte range of the file, see
+    /// [`packup_sync_file_range`].
+    ///
+    /// [`packup_sync_file_range`]: method@crate::Packer::packup_sync_file_range
+    #[derive(Debug)]
+    pub struct SyncFileRange {
+        fd: RawFd,
+        offset: u64,
+        len: u32,
+        flags: SyncRangeFlags,
+    }
+    |op, packer| packer.packup_sync_file_range(op.fd, op.offset, op.len, op.flags)
+}
+
+operation! {
+    /// The operation that accepts a connection, see [`packup_accept`]. The
+    /// address of the peer isn't retrieved, which can be got by
+    /// getpeername(2).
+    ///
+    /// [`packup_accept`]: method@crate::Packer::packup_accept
+    #[derive(Debug)]
+    pub struct Accept {
+        fd: RawFd,
+        flags: SockFlags,
+    }
+    |op, packer| packer.packup_accept(op.fd, None, op.flags)
+}
+
+operation! {
+    /// The operation that connects the socket, see [`packup_connect`].
+    ///
+    /// [`packup_connect`]: method@crate::Packer::packup_connect
+    pub struct Connect {
+        fd: RawFd,
+        addr: SockAddrStorage,
+    }
+    |op, packer| packer.packup_connect(op.fd, op.addr)
+}
+
+operation! {
+    /// The operation that manipulates the allocated space of the file, see
+    /// [`packup_fallocate`].
+    ///
+    /// [`packup_fallocate`]: method@crate::Packer::packup_fallocate
+    #[derive(Debug)]
+    pub struct Fallocate {
+        fd: RawFd,
+        offset: u64,
+        len: u64,
+        mode: FallocateMode,
+    }
+    |op, packer| packer.packup_fallocate(op.fd, op.offset, op.len, op.mode)
+}
+
+operation! {
+    /// The operation that opens the file, see [`packup_openat`].
+    ///
+    /// [`packup_openat`]: method@crate::Packer::packup_openat
+    #[derive(Debug)]
+    pub struct OpenAt {
+        dirfd: RawFd,
+        path: CString,
+        flags: OFlag,
+        mode: mode_t,
+    }
+    |op, packer| packer.packup_openat(op.dirfd, op.path, op.flags, op.mode)
+}
+
+operation! {
+    /// The operation that opens the file in the way of the `how`, see
+    /// [`packup_openat2`].
+    ///
+    /// [`packup_openat2`]: method@crate::Packer::packup_openat2
+    pub struct OpenAt2 {
+        dirfd: RawFd,
+        path: CString,
+        how: OpenHow,
+    }
+    |op, packer| packer.packup_openat2(op.dirfd, op.path, op.how)
+}
+
+operation! {
+    /// The operation that closes the file descriptor, see [`packup_close`].
+    ///
+    /// [`packup_close`]: method@crate::Packer::packup_close
+    #[derive(Debug)]
+    pub struct Close {
+        fd: RawFd,
+    }
+    |op, packer| packer.packup_close(op.fd)
+}
+
+operation! {
+    /// The operation that reads into the owned buffer, see
+    /// [`packup_read_owned`].
+    ///
+    /// [`packup_read_owned`]: method@crate::Packer::packup_read_owned
+    #[derive(Debug)]
+    pub struct Read<B: IoBufMut> {
+        fd: RawFd,
+        buf: B,
+        offset: u64,
+    }
+    |op, packer| packer.packup_read_owned(op.fd, op.buf, op.offset)
+}
+
+operation! {
+    /// The operation that writes the owned buffer, see
+    /// [`packup_write_owned`].
+    ///
+    /// [`packup_write_owned`]: method@crate::Packer::packup_write_owned
+    #[derive(Debug)]
+    pub struct Write<B: IoBuf> {
+        fd: RawFd,
+        buf: B,
+        offset: u64,
+    }
+    |op, packer| packer.packup_write_owned(op.fd, op.buf, op.offset)
+}
+
+operation! {
+    /// The operation that reads into the owned buffers in order, see
+    /// [`packup_readv_owned`].
+    ///
+    /// [`packup_readv_owned`]: method@crate::Packer::packup_readv_owned
+    #[derive(Debug)]
+    pub struct Readv<B: IoBufMut> {
+        fd: RawFd,
+        bufs: Vec<B>,
+        offset: u64,
+    }
+    |op, packer| packer.packup_readv_owned(op.fd, op.bufs, op.offset)
+}
+
+operation! {
+    /// The operation that writes the owned buffers in order, see
+    /// [`packup_writev_owned`].
+    ///
+    /// [`packup_writev_owned`]: method@crate::Packer::packup_writev_owned
+    #[derive(Debug)]
+    pub struct Writev<B: IoBuf> {
+        fd: RawFd,
+        bufs: Vec<B>,
+        offset: u64,
+    }
+    |op, packer| packer.packup_writev_owned(op.fd, op.bufs, op.offset)
+}
+
+operation! {
+    /// The operation that announces the access pattern of the file, see
+    /// [`packup_fadvise`].
+    ///
+    /// [`packup_fadvise`]: method@crate::Packer::packup_fadvise
+    #[derive(Debug)]
+    pub struct Fadvise {
+        fd: RawFd,
+        offset: u64,
+        len: u32,
+        advice: Advice,
+    }
+    |op, packer| packer.packup_fadvise(op.fd, op.offset, op.len, op.advice)
+}
+
+operation! {
+    /// The operation that sends the message in the owned buffer, see
+    /// [`packup_send_owned`].
+    ///
+    /// [`packup_send_owned`]: method@crate::Packer::packup_send_owned
+    #[derive(Debug)]
+    pub struct Send<B: IoBuf> {
+        fd: RawFd,
+        buf: B,
+        flags: MsgFlags,
+    }
+    |op, packer| packer.packup_send_owned(op.fd, op.buf, op.flags)
+}
+
+operation! {
+    /// The operation that receives the message into the owned buffer, see
+    /// [`packup_recv_owned`].
+    ///
+    /// [`packup_recv_owned`]: method@crate::Packer::packup_recv_owned
+    #[derive(Debug)]
+    pub struct Recv<B: IoBufMut> {
+        fd: RawFd,
+        buf: B,
+        flags: MsgFlags,
+    }
+    |op, packer| packer.packup_recv_owned(op.fd, op.buf, op.flags)
+}
+
+operation! {
+    /// The operation that moves data between the file descriptors, see
+    /// [`packup_splice`].
+    ///
+    /// [`packup_splice`]: method@crate::Packer::packup_splice
+    #[derive(Debug)]
+    pub struct Splice {
+        fd_in: Fd,
+        off_in: Option<u64>,
+        fd_out: RawFd,
+        off_out: Option<u64>,
+        len: u32,
+        flags: SpliceFlags,
+    }
+    |op, packer| {
+        packer.packup_splice(op.fd_in, op.off_in, op.fd_out, op.off_out, op.len, op.flags)
+    }
+}
+
+operation! {
+    /// The operation that shuts down the connection, see
+    /// [`packup_shutdown`].
+    ///
+    /// [`packup_shutdown`]: method@crate::Packer::packup_shutdown
+    #[derive(Debug)]
+    pub struct Shutdown {
+        fd: RawFd,
+        how: net::Shutdown,
+    }
+    |op, packer| packer.packup_shutdown(op.fd, op.how)
+}
+
+operation! {
+    /// The operation that creates the socket, see [`packup_socket`].
+    ///
+    /// [`packup_socket`]: method@crate::Packer::packup_socket
+    #[derive(Debug)]
+    pub struct Socket {
+        domain: Domain,
+        ty: SockType,
+        protocol: Protocol,
+        flags: SockFlags,
+    }
+    |op, packer| packer.packup_socket(op.domain, op.ty, op.protocol, op.flags)
+}
+
+operation! {
+    /// The operation that binds the socket to the address, see
+    /// [`packup_bind`].
+    ///
+    /// [`packup_bind`]: method@crate::Packer::packup_bind
+    pub struct Bind {
+        fd: RawFd,
+        addr: SockAddrStorage,
+    }
+    |op, packer| packer.packup_bind(op.fd, op.addr)
+}
+
+operation! {
+    /// The operation that listens for connections on the socket, see
+    /// [`packup_listen`].
+    ///
+    /// [`packup_listen`]: method@crate::Packer::packup_listen
+    #[derive(Debug)]
+    pub struct Listen {
+        fd: RawFd,
+        backlog: u32,
+    }
+    |op, packer| packer.packup_listen(op.fd, op.backlog)
+}
+
+operation! {
+    /// The operation that truncates the file, see [`packup_ftruncate`].
+    ///
+    /// [`packup_ftruncate`]: method@crate::Packer::packup_ftruncate
+    #[derive(Debug)]
+    pub struct Ftruncate {
+        fd: RawFd,
+        len: u64,
+    }
+    |op, packer| packer.packup_ftruncate(op.fd, op.len)
+}
+
+operation! {
+    /// The operation that duplicates data between the pipes, see
+    /// [`packup_tee`].
+    ///
+    /// [`packup_tee`]: method@crate::Packer::packup_tee
+    #[derive(Debug)]
+    pub struct Tee {
+        fd_in: Fd,
+        fd_out: RawFd,
+        len: u32,
+        flags: SpliceFlags,
+    }
+    |op, packer| packer.packup_tee(op.fd_in, op.fd_out, op.len, op.flags)
+}
+
+operation! {
+    /// The operation that controls the interest list of the epoll instance,
+    /// see [`packup_epoll_ctl`].
+    ///
+    /// [`packup_epoll_ctl`]: method@crate::Packer::packup_epoll_ctl
+    pub struct EpollCtl {
+        epfd: RawFd,
+        op: EpollOp,
+        fd: RawFd,
+        event: EpollEvent,
+    }
+    |op, packer| packer.packup_epoll_ctl(op.epfd, op.op, op.fd, op.event)
+}
+
+operation! {
+    /// The operation that renames the file, see [`packup_renameat`].
+    ///
+    /// [`packup_renameat`]: method@crate::Packer::packup_renameat
+    #[derive(Debug)]
+    pub struct RenameAt {
+        olddirfd: RawFd,
+        oldpath: CString,
+        newdirfd: RawFd,
+        newpath: CString,
+        flags: RenameFlags,
+    }
+    |op, packer| {
+        packer.packup_renameat(op.olddirfd, op.oldpath, op.newdirfd, op.newpath, op.flags)
+    }
+}
+
+operation! {
+    /// The operation that removes the path, see [`packup_unlinkat`].
+    ///
+    /// [`packup_unlinkat`]: method@crate::Packer::packup_unlinkat
+    #[derive(Debug)]
+    pub struct UnlinkAt {
+        dirfd: RawFd,
+        path: CString,
+        flags: AtFlags,
+    }
+    |op, packer| packer.packup_unlinkat(op.dirfd, op.path, op.flags)
+}
+
+operation! {
+    /// The operation that creates the directory, see [`packup_mkdirat`].
+    ///
+    /// [`packup_mkdirat`]: method@crate::Packer::packup_mkdirat
+    #[derive(Debug)]
+    pub struct MkdirAt {
+        dirfd: RawFd,
+        path: CString,
+        mode: mode_t,
+    }
+    |op, packer| packer.packup_mkdirat(op.dirfd, op.path, op.mode)
+}
+
+operation! {
+    /// The operation that creates the symbolic link, see
+    /// [`packup_symlinkat`].
+    ///
+    /// [`packup_symlinkat`]: method@crate::Packer::packup_symlinkat
+    #[derive(Debug)]
+    pub struct SymlinkAt {
+        target: CString,
+        newdirfd: RawFd,
+        linkpath: CString,
+    }
+    |op, packer| packer.packup_symlinkat(op.target, op.newdirfd, op.linkpath)
+}
+
+operation! {
+    /// The operation that creates the hard link, see [`packup_linkat`].
+    ///
+    /// [`packup_linkat`]: method@crate::Packer::packup_linkat
+    #[derive(Debug)]
+    pub struct LinkAt {
+        olddirfd: RawFd,
+        oldpath: CString,
+        newdirfd: RawFd,
+        newpath: CString,
+        flags: AtFlags,
+    }
+    |op, packer| {
+        packer.packup_linkat(op.olddirfd, op.oldpath, op.newdirfd, op.newpath, op.flags)
+    }
+}
+
+operation! {
+    /// The operation that sets the extended attribute of the file, see
+    /// [`packup_setxattr`].
+    ///
+    /// [`packup_setxattr`]: method@crate::Packer::packup_setxattr
+    #[derive(Debug)]
+    pub struct SetXattr<B: IoBuf> {
+        path: CString,
+        name: CString,
+        value: B,
+        flags: XattrFlags,
+    }
+    |op, packer| packer.packup_setxattr(op.path, op.name, op.value, op.flags)
+}
+
+operation! {
+    /// The operation that sets the extended attribute of the open file, see
+    /// [`packup_fsetxattr`].
+    ///
+    /// [`packup_fsetxattr`]: method@crate::Packer::packup_fsetxattr
+    #[derive(Debug)]
+    pub struct FSetXattr<B: IoBuf> {
+        fd: RawFd,
+        name: CString,
+        value: B,
+        flags: XattrFlags,
+    }
+    |op, packer| packer.packup_fsetxattr(op.fd, op.name, op.value, op.flags)
+}
+
+operation! {
+    /// The operation that gets the extended attribute of the file, see
+    /// [`packup_getxattr`].
+    ///
+    /// [`packup_getxattr`]: method@crate::Packer::packup_getxattr
+    #[derive(Debug)]
+    pub struct GetXattr<B: IoBufMut> {
+        path: CString,
+        name: CString,
+        value: B,
+    }
+    |op, packer| packer.packup_getxattr(op.path, op.name, op.value)
+}
+
+operation! {
+    /// The operation that gets the extended attribute of the open file, see
+    /// [`packup_fgetxattr`].
+    ///
+    /// [`packup_fgetxattr`]: method@crate::Packer::packup_fgetxattr
+    #[derive(Debug)]
+    pub struct FGetXattr<B: IoBufMut> {
+        fd: RawFd,
+        name: CString,
+        value: B,
+    }
+    |op, packer| packer.packup_fgetxattr(op.fd, op.name, op.value)
+}
+
+operation! {
+    /// The operation that passes the command to the file, see
+    /// [`packup_uring_cmd`].
+    ///
+    /// [`packup_uring_cmd`]: method@crate::Packer::packup_uring_cmd
+    #[derive(Debug)]
+    pub struct UringCmd {
+        fd: RawFd,
+        cmd_op: u32,
+        cmd: Vec<u8>,
+    }
+    |op, packer| packer.packup_uring_cmd(op.fd, op.cmd_op, &op.cmd)
+}
+
+operation! {
+    /// The operation that gets the value of the socket option, see
+    /// [`packup_getsockopt`].
+    ///
+    /// [`packup_getsockopt`]: method@crate::Packer::packup_getsockopt
+    #[derive(Debug)]
+    pub struct GetSockOpt<B: IoBufMut> {
+        fd: RawFd,
+        level: c_int,
+        optname: c_int,
+        optval: B,
+    }
+    |op, packer| packer.packup_getsockopt(op.fd, op.level, op.optname, op.optval)
+}
+
+operation! {
+    /// The operation that sets the value of the socket option, see
+    /// [`packup_setsockopt`].
+    ///
+    /// [`packup_setsockopt`]: method@crate::Packer::packup_setsockopt
+    #[derive(Debug)]
+    pub struct SetSockOpt<B: IoBuf> {
+        fd: RawFd,
+        level: c_int,
+        optname: c_int,
+        optval: B,
+    }
+    |op, packer| packer.packup_setsockopt(op.fd, op.level, op.optname, op.optval)
+}
@@ -0,0 +1,420 @@
+use std::{any::Any, convert::TryFrom, mem};
+
+use libc::iovec;
+
+/// A buffer which can be owned by an operation while it's in flight (e.g.
+/// [`packup_write_owned`]).
+///
+/// The buffer is moved into the io_uring instance when the operation is
+/// packed up, and handed back by [`Cqe::take_buf`] once it's completed, so
+/// that it can't be dropped while the kernel accesses it.
+///
+/// # Safety
+///
+/// The memory pointed by [`stable_ptr`] must stay valid and unmoved while the
+/// buffer is moved around, and the first [`bytes_init`] bytes of it must be
+/// initialized.
+///
+/// [`packup_write_owned`]: method@crate::Packer::packup_write_owned
+/// [`Cqe::take_buf`]: method@crate::Cqe::take_buf
+/// [`stable_ptr`]: method@Self::stable_ptr
+/// [`bytes_init`]: method@Self::bytes_init
+pub unsafe trait IoBuf: Send + 'static {
+    /// Returns the pointer to the start of the buffer.
+    fn stable_ptr(&self) -> *const u8;
+
+    /// Returns the number of initialized bytes, which are written by the
+    /// operations.
+    fn bytes_init(&self) -> usize;
+
+    /// Returns the total number of bytes, which can be filled by the
+    /// operations.
+    fn bytes_total(&self) -> usize;
+}
+
+/// A mutable [`IoBuf`], which can be filled by an operation while it's in
+/// flight (e.g. [`packup_read_owned`]).
+///
+/// # Safety
+///
+/// The memory pointed by [`stable_mut_ptr`] must stay valid and unmoved while
+/// the buffer is moved around, and it must be [`bytes_total`] bytes long.
+///
+/// [`packup_read_owned`]: method@crate::Packer::packup_read_owned
+/// [`stable_mut_ptr`]: method@Self::stable_mut_ptr
+/// [`bytes_total`]: method@IoBuf::bytes_total
+pub unsafe trait IoBufMut: IoBuf {
+    /// Returns the mutable pointer to the start of the buffer.
+    fn stable_mut_ptr(&mut self) -> *mut u8;
+
+    /// Updates the number of initialized bytes, if `pos` is beyond it.
+    ///
+    /// # Safety
+    ///
+    /// The first `pos` bytes of the buffer must be initialized.
+    unsafe fn set_init(&mut self, pos: usize);
+}
+
+unsafe impl IoBuf for Vec<u8> {
+    #[inline]
+    fn stable_ptr(&self) -> *const u8 {
+        self.as_ptr()
+    }
+
+    #[inline]
+    fn bytes_init(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn bytes_total(&self) -> usize {
+        self.capacity()
+    }
+}
+
+unsafe impl IoBufMut for Vec<u8> {
+    #[inline]
+    fn stable_mut_ptr(&mut self) -> *mut u8 {
+        self.as_mut_ptr()
+    }
+
+    #[inline]
+    unsafe fn set_init(&mut self, pos: usize) {
+        if self.len() < pos {
+            self.set_len(pos);
+        }
+    }
+}
+
+unsafe impl IoBuf for Box<[u8]> {
+    #[inline]
+    fn stable_ptr(&self) -> *const u8 {
+        self.as_ptr()
+    }
+
+    #[inline]
+    fn bytes_init(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn bytes_total(&self) -> usize {
+        self.len()
+    }
+}
+
+unsafe impl IoBufMut for Box<[u8]> {
+    #[inline]
+    fn stable_mut_ptr(&mut self) -> *mut u8 {
+        self.as_mut_ptr()
+    }
+
+    #[inline]
+    unsafe fn set_init(&mut self, _pos: usize) {}
+}
+
+unsafe impl IoBuf for &'static [u8] {
+    #[inline]
+    fn stable_ptr(&self) -> *const u8 {
+        self.as_ptr()
+    }
+
+    #[inline]
+    fn bytes_init(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn bytes_total(&self) -> usize {
+        self.len()
+    }
+}
+
+/// The iovecs describing the owned buffers of a vectored operation (e.g.
+/// [`packup_readv_owned`]), which are kept along with the buffers.
+///
+/// [`packup_readv_owned`]: method@crate::Packer::packup_readv_owned
+pub(crate) struct IoVecs(Box<[iovec]>);
+
+impl IoVecs {
+    /// Describe the buffers by their pointers and lengths.
+    pub(crate) fn new(bufs: impl Iterator<Item = (*mut u8, usize)>) -> Self {
+        Self(
+            bufs.map(|(ptr, len)| iovec {
+                iov_base: ptr as _,
+                iov_len: len,
+            })
+            .collect(),
+        )
+    }
+
+    #[inline]
+    pub(crate) fn as_ptr(&self) -> *const iovec {
+        self.0.as_ptr()
+    }
+
+    #[inline]
+    pub(crate) fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+// The iovecs only point to the buffers which are owned along with them.
+unsafe impl Send for IoVecs {}
+
+/// The most significant bit of `user_data`, which marks the SQE of the
+/// operation tracked by the [`Registry`] (e.g. owning a buffer). The rest of
+/// bits identify the operation, the generation of the slot in the upper half
+/// and the index of it in the lower half.
+pub(crate) const OWNED_USER_DATA: u64 = 1 << 63;
+
+/// The `user_data` handed back for the events of the internal operations
+/// (e.g. cancellation), which can't be packed by users and never identifies a
+/// tracked operation.
+pub(crate) const INTERNAL_USER_DATA: u64 = !0;
+
+// The generation takes the upper half of `user_data` except the most
+// significant bit.
+const GEN_MASK: u32 = !0 >> 1;
+
+// The buffer owned by an operation, whose type is erased.
+type Buf = Box<dyn Any + Send>;
+
+/// Operations in flight which are tracked until their completion events are
+/// reaped, along with the buffers owned by them.
+#[derive(Default)]
+pub(crate) struct Registry {
+    slots: Vec<Slot>,
+    vacant: Vec<u32>,
+}
+
+// The generation is bumped whenever the slot is vacated, so that the stale
+// `user_data` (e.g. of the multishot operation which is packed up over the
+// owning one) never refers to the buffer of another operation.
+#[derive(Default)]
+struct Slot {
+    gen: u32,
+    entry: Option<Entry>,
+}
+
+struct Entry {
+    user_data: u64,
+    buf: Option<Box<dyn Any + Send>>,
+    // The data which is read by the operation besides the buffer (e.g. the
+    // paths), and dropped at completion time.
+    held: Option<Box<dyn Any + Send>>,
+    // Updates the number of initialized bytes of the buffer, which is filled
+    // by the operation.
+    fill: Option<unsafe fn(&mut (dyn Any + Send), usize)>,
+    // The id of the buffer group which the operation selects a buffer from.
+    group: Option<u16>,
+}
+
+impl Registry {
+    /// Keep the `buf` which is read or filled in by the operation, and handed
+    /// back at completion time. Returns the `user_data` for the operation.
+    pub(crate) fn insert<T: Send + 'static>(&mut self, user_data: u64, buf: T) -> u64 {
+        self.insert_entry(Entry {
+            user_data,
+            buf: Some(Box::new(buf)),
+            held: None,
+            fill: None,
+            group: None,
+        })
+    }
+
+    /// Keep the `buf` which is filled by the operation. Returns the
+    /// `user_data` for the operation.
+    pub(crate) fn insert_mut<B: IoBufMut>(&mut self, user_data: u64, buf: B) -> u64 {
+        unsafe fn fill<B: IoBufMut>(buf: &mut (dyn Any + Send), len: usize) {
+            let buf = buf.downcast_mut::<B>().unwrap();
+            // The result may exceed the buffer (e.g. the size of the extended
+            // attribute which is queried with an empty buffer).
+            let len = len.min(buf.bytes_total());
+            buf.set_init(len);
+        }
+
+        self.insert_filled(user_data, buf, fill::<B>)
+    }
+
+    /// Keep the `buf` which is filled by the operation, whose result is
+    /// applied to the `buf` by the `fill` at completion time. Returns the
+    /// `user_data` for the operation.
+    pub(crate) fn insert_filled<T: Send + 'static>(
+        &mut self,
+        user_data: u64,
+        buf: T,
+        fill: unsafe fn(&mut (dyn Any + Send), usize),
+    ) -> u64 {
+        self.insert_entry(Entry {
+            user_data,
+            buf: Some(Box::new(buf)),
+            held: None,
+            fill: Some(fill),
+            group: None,
+        })
+    }
+
+    /// Track the operation which doesn't own a buffer. Returns the
+    /// `user_data` for the operation.
+    pub(crate) fn insert_tracked(&mut self, user_data: u64) -> u64 {
+        self.insert_entry(Entry {
+            user_data,
+            buf: None,
+            held: None,
+            fill: None,
+            group: None,
+        })
+    }
+
+    /// Keep the `data` which is read by the operation, but not handed back
+    /// at completion time. Returns the `user_data` for the operation.
+    pub(crate) fn insert_held<T: Send + 'static>(&mut self, user_data: u64, data: T) -> u64 {
+        self.insert_entry(Entry {
+            user_data,
+            buf: None,
+            held: Some(Box::new(data)),
+            fill: None,
+            group: None,
+        })
+    }
+
+    /// Keep the `data` which is read by the operation identified by `owned`
+    /// as well as its buffer.
+    pub(crate) fn hold<T: Send + 'static>(&mut self, owned: u64, data: T) {
+        if let Some(entry) = self.get_mut(owned).and_then(|slot| slot.entry.as_mut()) {
+            entry.held = Some(Box::new(data));
+        }
+    }
+
+    /// Record the id of the buffer group `bgid` which the operation identified
+    /// by `owned` selects a buffer from.
+    pub(crate) fn select(&mut self, owned: u64, bgid: u16) {
+        if let Some(entry) = self.get_mut(owned).and_then(|slot| slot.entry.as_mut()) {
+            entry.group = Some(bgid);
+        }
+    }
+
+    fn insert_entry(&mut self, entry: Entry) -> u64 {
+        let idx = self.vacant.pop().unwrap_or_else(|| {
+            self.slots.push(Slot::default());
+            (self.slots.len() - 1) as _
+        });
+        let slot = &mut self.slots[idx as usize];
+        slot.entry = Some(entry);
+        OWNED_USER_DATA | (slot.gen as u64) << 32 | idx as u64
+    }
+
+    /// Replace the `user_data` of the operation identified by `owned`, which
+    /// is handed back at completion time.
+    pub(crate) fn set_user_data(&mut self, owned: u64, user_data: u64) {
+        if let Some(entry) = self.get_mut(owned).and_then(|slot| slot.entry.as_mut()) {
+            entry.user_data = user_data;
+        }
+    }
+
+    /// Returns `true` if the operation identified by `owned` is in flight.
+    pub(crate) fn contains(&mut self, owned: u64) -> bool {
+        matches!(self.get_mut(owned), Some(Slot { entry: Some(_), .. }))
+    }
+
+    /// Stop tracking the operation identified by `owned`, which is completed
+    /// with the result `res`. Returns the `user_data` of the operation and the
+    /// buffer if any, or `None` if there's no such operation.
+    pub(crate) fn complete(
+        &mut self,
+        owned: u64,
+        res: i32,
+    ) -> Option<(u64, Option<Box<dyn Any + Send>>)> {
+        let slot = self.get_mut(owned)?;
+        let mut entry = slot.entry.take()?;
+        slot.gen = slot.gen.wrapping_add(1) & GEN_MASK;
+        self.vacant.push(owned as u32);
+
+        if let (Some(fill), Some(buf), Ok(len)) =
+            (entry.fill, entry.buf.as_mut(), usize::try_from(res))
+        {
+            // The kernel has filled `len` bytes of the buffer.
+            unsafe { fill(buf.as_mut(), len) };
+        }
+        Some((entry.user_data, entry.buf))
+    }
+
+    /// Account for the event of the operation identified by `owned`, which is
+    /// completed with the result `res`. If `more` events will follow (e.g.
+    /// multishot operations), the operation is still tracked and nothing is
+    /// handed back but the `user_data`. Returns the `user_data` of the
+    /// operation, the buffer if any and the id of the selected buffer group if
+    /// any, or `None` if there's no such operation.
+    pub(crate) fn reap(
+        &mut self,
+        owned: u64,
+        res: i32,
+        more: bool,
+    ) -> Option<(u64, Option<Buf>, Option<u16>)> {
+        let entry = self.get_mut(owned)?.entry.as_ref()?;
+        let (user_data, group) = (entry.user_data, entry.group);
+        if more {
+            Some((user_data, None, group))
+        } else {
+            let (user_data, buf) = self.complete(owned, res)?;
+            Some((user_data, buf, group))
+        }
+    }
+
+    #[inline]
+    fn get_mut(&mut self, owned: u64) -> Option<&mut Slot> {
+        let slot = self.slots.get_mut(owned as u32 as usize)?;
+        if slot.gen as u64 == (owned & !OWNED_USER_DATA) >> 32 {
+            Some(slot)
+        } else {
+            None
+        }
+    }
+}
+
+impl Drop for Registry {
+    fn drop(&mut self) {
+        // The operations in flight may still access the buffers after the
+        // io_uring instance is closed, so they are leaked.
+        for entry in self.slots.drain(..).filter_map(|slot| slot.entry) {
+            mem::forget(entry.buf);
+            mem::forget(entry.held);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Registry;
+
+    #[test]
+    fn stale_user_data() {
+        let mut registry = Registry::default();
+        let first = registry.insert(1, vec![1u8]);
+        assert!(registry.complete(first, 0).is_some());
+
+        // The slot is reused with another generation.
+        let second = registry.insert(2, vec![2u8]);
+        assert_ne!(first, second);
+        assert!(registry.complete(first, 0).is_none());
+
+        let (user_data, buf) = registry.complete(second, 0).unwrap();
+        assert_eq!(user_data, 2);
+        assert_eq!(*buf.unwrap().downcast::<Vec<u8>>().unwrap(), [2]);
+    }
+
+    #[test]
+    fn reap_more_events() {
+        let mut registry = Registry::default();
+        let owned = registry.insert_tracked(1);
+        registry.select(owned, 7);
+
+        // It's tracked until the last event.
+        assert_eq!(registry.reap(owned, 4, true).unwrap().2, Some(7));
+        assert!(registry.contains(owned));
+        let (user_data, _, group) = registry.reap(owned, 4, false).unwrap();
+        assert_eq!((user_data, group), (1, Some(7)));
+        assert!(!registry.contains(owned));
+    }
+}
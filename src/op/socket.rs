@@ -0,0 +1,94 @@
+use libc::c_int;
+
+/// A communication domain of a socket, which selects the protocol family.
+///
+/// See socket(2) for the available domains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Domain(c_int);
+
+impl Domain {
+    /// IPv4 Internet protocols.
+    pub const INET: Self = Self(libc::AF_INET);
+    /// IPv6 Internet protocols.
+    pub const INET6: Self = Self(libc::AF_INET6);
+    /// Local communication, a.k.a. Unix domain.
+    pub const UNIX: Self = Self(libc::AF_UNIX);
+    /// Kernel user interface device.
+    pub const NETLINK: Self = Self(libc::AF_NETLINK);
+    /// Low-level packet interface.
+    pub const PACKET: Self = Self(libc::AF_PACKET);
+}
+
+impl From<c_int> for Domain {
+    fn from(domain: c_int) -> Self {
+        Self(domain)
+    }
+}
+
+impl From<Domain> for c_int {
+    fn from(domain: Domain) -> Self {
+        domain.0
+    }
+}
+
+/// A type of a socket, which specifies the communication semantics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SockType(c_int);
+
+impl SockType {
+    /// Sequenced, reliable, two-way, connection-based byte streams.
+    pub const STREAM: Self = Self(libc::SOCK_STREAM);
+    /// Connectionless, unreliable messages of a fixed maximum length.
+    pub const DGRAM: Self = Self(libc::SOCK_DGRAM);
+    /// Sequenced, reliable, two-way, connection-based datagrams of a fixed
+    /// maximum length.
+    pub const SEQPACKET: Self = Self(libc::SOCK_SEQPACKET);
+    /// Raw network protocol access.
+    pub const RAW: Self = Self(libc::SOCK_RAW);
+}
+
+impl From<c_int> for SockType {
+    fn from(ty: c_int) -> Self {
+        Self(ty)
+    }
+}
+
+impl From<SockType> for c_int {
+    fn from(ty: SockType) -> Self {
+        ty.0
+    }
+}
+
+/// A particular protocol to be used with a socket.
+///
+/// Normally only a single protocol exists to support a particular socket type
+/// within a given protocol family, in which case it can be [`DEFAULT`].
+///
+/// [`DEFAULT`]: Self::DEFAULT
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Protocol(c_int);
+
+impl Protocol {
+    /// The default protocol of the socket type.
+    pub const DEFAULT: Self = Self(0);
+    /// Transmission Control Protocol.
+    pub const TCP: Self = Self(libc::IPPROTO_TCP);
+    /// User Datagram Protocol.
+    pub const UDP: Self = Self(libc::IPPROTO_UDP);
+    /// Internet Control Message Protocol.
+    pub const ICMP: Self = Self(libc::IPPROTO_ICMP);
+    /// Internet Control Message Protocol for IPv6.
+    pub const ICMPV6: Self = Self(libc::IPPROTO_ICMPV6);
+}
+
+impl From<c_int> for Protocol {
+    fn from(protocol: c_int) -> Self {
+        Self(protocol)
+    }
+}
+
+impl From<Protocol> for c_int {
+    fn from(protocol: Protocol) -> Self {
+        protocol.0
+    }
+}
@@ -1,21 +1,71 @@
 use std::{
+    any::Any,
+    ffi::CString,
     io::{IoSlice, IoSliceMut},
-    os::unix::io::RawFd,
+    mem,
+    net::Shutdown,
+    os::unix::io::{AsRawFd, RawFd},
+    slice,
+    sync::Arc,
 };
 
-use crate::sys::{
-    io_uring_sqe, IORING_OP_FSYNC, IORING_OP_NOP, IORING_OP_POLL_ADD, IORING_OP_READV,
-    IORING_OP_READ_FIXED, IORING_OP_WRITEV, IORING_OP_WRITE_FIXED,
+use libc::{c_int, c_void, futex_waitv, mode_t, open_how};
+
+use crate::{
+    sys::{
+        io_uring_sqe, IORING_ACCEPT_MULTISHOT, IORING_FILE_INDEX_ALLOC, IORING_MSG_DATA,
+        IORING_MSG_SEND_FD, IORING_OP_ACCEPT, IORING_OP_ASYNC_CANCEL, IORING_OP_BIND,
+        IORING_OP_CLOSE, IORING_OP_CONNECT, IORING_OP_EPOLL_CTL, IORING_OP_EPOLL_WAIT,
+        IORING_OP_FADVISE, IORING_OP_FALLOCATE, IORING_OP_FGETXATTR, IORING_OP_FIXED_FD_INSTALL,
+        IORING_OP_FSETXATTR, IORING_OP_FSYNC, IORING_OP_FTRUNCATE, IORING_OP_FUTEX_WAIT,
+        IORING_OP_FUTEX_WAITV, IORING_OP_FUTEX_WAKE, IORING_OP_GETXATTR, IORING_OP_LINKAT,
+        IORING_OP_LISTEN, IORING_OP_MADVISE, IORING_OP_MKDIRAT, IORING_OP_MSG_RING, IORING_OP_NOP,
+        IORING_OP_OPENAT, IORING_OP_OPENAT2, IORING_OP_POLL_ADD, IORING_OP_PROVIDE_BUFFERS,
+        IORING_OP_READ, IORING_OP_READV, IORING_OP_READ_FIXED, IORING_OP_RECV, IORING_OP_RECVMSG,
+        IORING_OP_REMOVE_BUFFERS, IORING_OP_RENAMEAT, IORING_OP_SEND, IORING_OP_SENDMSG,
+        IORING_OP_SENDMSG_ZC, IORING_OP_SEND_ZC, IORING_OP_SETXATTR, IORING_OP_SHUTDOWN,
+        IORING_OP_SOCKET, IORING_OP_SPLICE, IORING_OP_STATX, IORING_OP_SYMLINKAT,
+        IORING_OP_SYNC_FILE_RANGE, IORING_OP_TEE, IORING_OP_TIMEOUT, IORING_OP_UNLINKAT,
+        IORING_OP_URING_CMD, IORING_OP_WAITID, IORING_OP_WRITE, IORING_OP_WRITEV,
+        IORING_OP_WRITE_FIXED, IORING_RECVSEND_BUNDLE, IORING_RECVSEND_FIXED_BUF,
+        IORING_RECV_MULTISHOT, IORING_TIMEOUT_MULTISHOT, IOSQE_BUFFER_SELECT,
+        IOSQE_CQE_SKIP_SUCCESS, IOSQE_FIXED_FILE, SOCKET_URING_OP_GETSOCKOPT,
+        SOCKET_URING_OP_SETSOCKOPT, SOCKET_URING_OP_SIOCINQ, SOCKET_URING_OP_SIOCOUTQ,
+        SPLICE_F_FD_IN_FIXED,
+    },
+    BufRing, Uring,
 };
 
-use super::{FsyncFlags, PollEvent};
+use super::{
+    Advice, AtFlags, BufSelect, BufferGroup, Domain, EpollEvent, EpollOp, FallocateMode, Fd,
+    FsyncFlags, Futex, FutexFlags, FutexWaitV, InstallFlags, IoBuf, IoBufMut, IoVecs, MsgFlags,
+    MsgHdr, OFlag, OpenHow, PollEvent, Protocol, Registry, RenameFlags, SockAddrStorage, SockFlags,
+    SockType, SpliceFlags, Statx, StatxMask, SyncRangeFlags, Timespec, WaitId, WaitInfo,
+    WaitOptions, XattrFlags, INTERNAL_USER_DATA, OWNED_USER_DATA,
+};
 
 /// Pack data into a SQE(Submission Queue Entry).
-pub struct Packer<'a>(&'a mut io_uring_sqe);
+///
+/// It holds the extra 64 bytes of the SQE as well, if the SQ is set up with
+/// [`Builder::big_sqe`].
+///
+/// [`Builder::big_sqe`]: method@crate::Builder::big_sqe
+pub struct Packer<'a>(
+    &'a mut io_uring_sqe,
+    Option<&'a mut [u8; 64]>,
+    &'a Uring,
+    // IOSQE_* flags required by the operation, which are kept by `flags`.
+    u8,
+);
 
 impl<'a> Packer<'a> {
-    /// Create a new [`Packer`] with the given mutable reference to SQE.
-    pub(crate) fn new(sqe: &'a mut io_uring_sqe) -> Self {
+    /// Create a new [`Packer`] with the given mutable reference to SQE, its
+    /// extension if any, and the io_uring instance which it belongs to.
+    pub(crate) fn new(
+        sqe: &'a mut io_uring_sqe,
+        ext: Option<&'a mut [u8; 64]>,
+        uring: &'a Uring,
+    ) -> Self {
         sqe.flags = 0;
         sqe.ioprio = 0;
         sqe.__bindgen_anon_3.rw_flags = 0;
@@ -24,20 +74,57 @@ impl<'a> Packer<'a> {
         sqe.personality = 0;
         sqe.__bindgen_anon_5.file_index = 0;
         sqe.__pad2 = [0, 0];
-        Self(sqe)
+        Self(sqe, ext, uring, 0)
     }
 
     /// Pack `user_data` which to be passed back at completion time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the most significant bit of `user_data` is set, which is
+    /// reserved for the operations owning buffers (e.g.
+    /// [`packup_read_owned`]).
+    ///
+    /// **Breaking change:** the bit was passed through like the others before
+    /// the owned operations were added. Callers which used it for their own
+    /// tagging must move it to another bit.
+    ///
+    /// [`packup_read_owned`]: method@Self::packup_read_owned
     #[inline]
     pub fn user_data(&mut self, user_data: u64) -> &mut Self {
-        self.0.user_data = user_data;
+        assert_user_data(user_data);
+        if self.0.user_data & OWNED_USER_DATA != 0 {
+            // It's passed back in place of the reserved one.
+            self.2.owned().set_user_data(self.0.user_data, user_data);
+        } else {
+            self.0.user_data = user_data;
+        }
         self
     }
 
     /// Pack IOSQE_* `flags`.
+    ///
+    /// They replace the flags packed before, except the ones required by the
+    /// operation (e.g. `IOSQE_BUFFER_SELECT` by [`packup_read_select`]), which
+    /// are kept.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `IOSQE_CQE_SKIP_SUCCESS` is set for the operation owning a
+    /// buffer (e.g. [`packup_read_owned`]), selecting a provided buffer (e.g.
+    /// [`packup_read_select`]) or tracked by a [`Scope`]. The operation is
+    /// tracked until its event is reaped, so it would be never released if the
+    /// event was skipped.
+    ///
+    /// [`packup_read_select`]: method@Self::packup_read_select
+    /// [`packup_read_owned`]: method@Self::packup_read_owned
+    /// [`Scope`]: struct@crate::Scope
     #[inline]
     pub fn flags(&mut self, flags: u8) -> &mut Self {
-        self.0.flags = flags;
+        if self.0.user_data & OWNED_USER_DATA != 0 {
+            assert_skip_success(flags);
+        }
+        self.0.flags = flags | self.3;
         self
     }
 
@@ -59,8 +146,22 @@ impl<'a> Packer<'a> {
     /// to zero.
     ///
     /// **Available since kernel 5.1.**
+    ///
+    /// # Safety
+    ///
+    /// The kernel writes into the buffers described by the `bufs` after this
+    /// method returns. So the `bufs` and the buffers must be kept alive and
+    /// unmoved, and the buffers must not be accessed until the operation is
+    /// completed. Use [`packup_readv_owned`] instead, which owns the buffers.
+    ///
+    /// [`packup_readv_owned`]: method@Self::packup_readv_owned
     #[inline]
-    pub fn packup_read_vectored(&mut self, fd: RawFd, bufs: &mut [IoSliceMut<'_>], offset: u64) {
+    pub unsafe fn packup_read_vectored(
+        &mut self,
+        fd: RawFd,
+        bufs: &mut [IoSliceMut<'_>],
+        offset: u64,
+    ) {
         self.pack(
             IORING_OP_READV,
             fd,
@@ -77,8 +178,17 @@ impl<'a> Packer<'a> {
     /// set to zero.
     ///
     /// **Available since kernel 5.1.**
+    ///
+    /// # Safety
+    ///
+    /// The kernel reads the buffers described by the `bufs` after this method
+    /// returns. So the `bufs` and the buffers must be kept alive and unmoved
+    /// until the operation is completed. Use [`packup_writev_owned`] instead,
+    /// which owns the buffers.
+    ///
+    /// [`packup_writev_owned`]: method@Self::packup_writev_owned
     #[inline]
-    pub fn packup_write_vectored(&mut self, fd: RawFd, bufs: &[IoSlice<'_>], offset: u64) {
+    pub unsafe fn packup_write_vectored(&mut self, fd: RawFd, bufs: &[IoSlice<'_>], offset: u64) {
         self.pack(
             IORING_OP_WRITEV,
             fd,
@@ -88,6 +198,161 @@ impl<'a> Packer<'a> {
         );
     }
 
+    /// Pack up data for the operation that reads from the file descriptor `fd`
+    /// into the owned buffers `bufs` in order.
+    ///
+    /// It's similar to preadv2(2). Each buffer is filled from its start, up to
+    /// [`IoBuf::bytes_total`] bytes, before the next one. The `bufs` is kept
+    /// by the io_uring instance while the operation is in flight, and handed
+    /// back by [`Cqe::take_buf`] at completion time, whose initialized bytes
+    /// cover the read data. If the file is not seekable, `offset` must be set
+    /// to zero.
+    ///
+    /// **Available since kernel 5.1.**
+    ///
+    /// [`IoBuf::bytes_total`]: method@crate::op::IoBuf::bytes_total
+    /// [`Cqe::take_buf`]: method@crate::Cqe::take_buf
+    #[inline]
+    pub fn packup_readv_owned<B: IoBufMut>(&mut self, fd: RawFd, mut bufs: Vec<B>, offset: u64) {
+        unsafe fn fill<B: IoBufMut>(bufs: &mut (dyn Any + Send), len: usize) {
+            let bufs = bufs.downcast_mut::<Vec<B>>().unwrap();
+            let mut rest = len;
+            for buf in bufs {
+                let len = rest.min(buf.bytes_total());
+                buf.set_init(len);
+                rest -= len;
+            }
+        }
+
+        let iovecs = IoVecs::new(
+            bufs.iter_mut()
+                .map(|buf| (buf.stable_mut_ptr(), buf.bytes_total())),
+        );
+        self.pack(
+            IORING_OP_READV,
+            fd,
+            iovecs.as_ptr() as _,
+            iovecs.len() as _,
+            offset,
+        );
+        self.own(|owned, user_data| {
+            let user_data = owned.insert_filled(user_data, bufs, fill::<B>);
+            owned.hold(user_data, iovecs);
+            user_data
+        });
+    }
+
+    /// Pack up data for the operation that writes the initialized bytes of
+    /// the owned buffers `bufs` in order to the file descriptor `fd`.
+    ///
+    /// It's similar to pwritev2(2). The `bufs` is kept by the io_uring
+    /// instance while the operation is in flight, and handed back by
+    /// [`Cqe::take_buf`] at completion time. If the file is not seekable,
+    /// `offset` must be set to zero.
+    ///
+    /// **Available since kernel 5.1.**
+    ///
+    /// [`Cqe::take_buf`]: method@crate::Cqe::take_buf
+    #[inline]
+    pub fn packup_writev_owned<B: IoBuf>(&mut self, fd: RawFd, bufs: Vec<B>, offset: u64) {
+        let iovecs = IoVecs::new(
+            bufs.iter()
+                .map(|buf| (buf.stable_ptr() as _, buf.bytes_init())),
+        );
+        self.pack(
+            IORING_OP_WRITEV,
+            fd,
+            iovecs.as_ptr() as _,
+            iovecs.len() as _,
+            offset,
+        );
+        self.own(|owned, user_data| {
+            let user_data = owned.insert(user_data, bufs);
+            owned.hold(user_data, iovecs);
+            user_data
+        });
+    }
+
+    /// Pack up data for the operation that reads from the file descriptor `fd`
+    /// into the buffer `buf`.
+    ///
+    /// It's similar to pread(2). The `buf` must be kept alive until the
+    /// operation is completed, which is guaranteed by [`Scope::read`]. If the
+    /// file is not seekable, `offset` must be set to zero.
+    ///
+    /// **Available since kernel 5.6.**
+    ///
+    /// [`Scope::read`]: method@crate::Scope::read
+    #[inline]
+    pub(crate) fn packup_read(&mut self, fd: RawFd, buf: &mut [u8], offset: u64) {
+        self.pack(
+            IORING_OP_READ,
+            fd,
+            buf.as_mut_ptr() as _,
+            buf.len() as _,
+            offset,
+        );
+    }
+
+    /// Pack up data for the operation that writes the buffer `buf` to the file
+    /// descriptor `fd`.
+    ///
+    /// It's similar to pwrite(2). The `buf` must be kept alive until the
+    /// operation is completed, which is guaranteed by [`Scope::write`]. If the
+    /// file is not seekable, `offset` must be set to zero.
+    ///
+    /// **Available since kernel 5.6.**
+    ///
+    /// [`Scope::write`]: method@crate::Scope::write
+    #[inline]
+    pub(crate) fn packup_write(&mut self, fd: RawFd, buf: &[u8], offset: u64) {
+        self.pack(
+            IORING_OP_WRITE,
+            fd,
+            buf.as_ptr() as _,
+            buf.len() as _,
+            offset,
+        );
+    }
+
+    /// Pack up data for the operation that reads from the file descriptor `fd`
+    /// into the owned buffer `buf`.
+    ///
+    /// It's similar to pread(2). The `buf` is filled from its start, up to
+    /// [`IoBuf::bytes_total`] bytes. It's kept by the io_uring instance while
+    /// the operation is in flight, and handed back by [`Cqe::take_buf`] at
+    /// completion time, whose initialized bytes cover the read data. If the
+    /// file is not seekable, `offset` must be set to zero.
+    ///
+    /// **Available since kernel 5.6.**
+    ///
+    /// [`IoBuf::bytes_total`]: method@crate::op::IoBuf::bytes_total
+    /// [`Cqe::take_buf`]: method@crate::Cqe::take_buf
+    #[inline]
+    pub fn packup_read_owned(&mut self, fd: RawFd, mut buf: impl IoBufMut, offset: u64) {
+        let (ptr, len) = (buf.stable_mut_ptr(), buf.bytes_total());
+        self.pack(IORING_OP_READ, fd, ptr as _, len as _, offset);
+        self.own(|owned, user_data| owned.insert_mut(user_data, buf));
+    }
+
+    /// Pack up data for the operation that writes the initialized bytes of
+    /// the owned buffer `buf` to the file descriptor `fd`.
+    ///
+    /// It's similar to pwrite(2). The `buf` is kept by the io_uring instance
+    /// while the operation is in flight, and handed back by
+    /// [`Cqe::take_buf`] at completion time. If the file is not seekable,
+    /// `offset` must be set to zero.
+    ///
+    /// **Available since kernel 5.6.**
+    ///
+    /// [`Cqe::take_buf`]: method@crate::Cqe::take_buf
+    #[inline]
+    pub fn packup_write_owned(&mut self, fd: RawFd, buf: impl IoBuf, offset: u64) {
+        let (ptr, len) = (buf.stable_ptr(), buf.bytes_init());
+        self.pack(IORING_OP_WRITE, fd, ptr as _, len as _, offset);
+        self.own(|owned, user_data| owned.insert(user_data, buf));
+    }
+
     /// Pack up data for the operation that synchronize in-core state of the
     /// file referred to by the file descriptor `fd` with storage device.
     ///
@@ -106,6 +371,102 @@ impl<'a> Packer<'a> {
         self.0.__bindgen_anon_3.fsync_flags = flags.bits();
     }
 
+    /// Pack up data for the operation that synchronizes the byte range of
+    /// `len` bytes from `offset` of the file `fd` with the disk.
+    ///
+    /// It's similar to sync_file_range(2). If `len` is 0, all bytes from
+    /// `offset` through to the end of the file are synchronized.
+    ///
+    /// **Available since kernel 5.2.**
+    #[inline]
+    pub fn packup_sync_file_range(
+        &mut self,
+        fd: RawFd,
+        offset: u64,
+        len: u32,
+        flags: SyncRangeFlags,
+    ) {
+        self.pack(IORING_OP_SYNC_FILE_RANGE, fd, 0, len, offset);
+        self.0.__bindgen_anon_3.sync_range_flags = flags.bits();
+    }
+
+    /// Pack up data for the operation that manipulates the allocated disk
+    /// space of the byte range of `len` bytes from `offset` of the file `fd`.
+    ///
+    /// It's similar to fallocate(2).
+    ///
+    /// **Available since kernel 5.6.**
+    #[inline]
+    pub fn packup_fallocate(&mut self, fd: RawFd, offset: u64, len: u64, mode: FallocateMode) {
+        // The length is passed in `addr` and the mode in `len`.
+        self.pack(IORING_OP_FALLOCATE, fd, len, mode.bits(), offset);
+    }
+
+    /// Pack up data for the operation that truncates the file `fd` to the
+    /// size of `len` bytes.
+    ///
+    /// It's similar to ftruncate(2). If the file was larger, the extra data
+    /// is lost, and if it was shorter, it's extended with zeros.
+    ///
+    /// **Available since kernel 6.9.**
+    #[inline]
+    pub fn packup_ftruncate(&mut self, fd: RawFd, len: u64) {
+        self.pack(IORING_OP_FTRUNCATE, fd, 0, 0, len);
+    }
+
+    /// Pack up data for the operation that installs the fixed file at
+    /// `file_index` into the normal file table.
+    ///
+    /// The completion event result is the new file descriptor, which can be
+    /// taken by [`Cqe::into_fd`] and handed to the code that knows nothing
+    /// about the fixed file table. The fixed file is left untouched.
+    ///
+    /// **Available since kernel 6.8.**
+    ///
+    /// [`Cqe::into_fd`]: method@crate::Cqe::into_fd
+    #[inline]
+    pub fn packup_fixed_fd_install(&mut self, file_index: u32, flags: InstallFlags) {
+        self.pack(IORING_OP_FIXED_FD_INSTALL, file_index as _, 0, 0, 0);
+        // It only works on the fixed file.
+        self.require_flags(IOSQE_FIXED_FILE);
+        self.0.__bindgen_anon_3.install_fd_flags = flags.bits();
+    }
+
+    /// Pack up data for the operation that announces the `advice` about the
+    /// access pattern of the byte range of `len` bytes from `offset` of the
+    /// file `fd`.
+    ///
+    /// It's similar to posix_fadvise(2). If `len` is 0, the advice applies to
+    /// all bytes from `offset` through to the end of the file.
+    ///
+    /// **Available since kernel 5.6.**
+    #[inline]
+    pub fn packup_fadvise(&mut self, fd: RawFd, offset: u64, len: u32, advice: Advice) {
+        self.pack(IORING_OP_FADVISE, fd, 0, len, offset);
+        self.0.__bindgen_anon_3.fadvise_advice = i32::from(advice) as _;
+    }
+
+    /// Pack up data for the operation that announces the `advice` about the
+    /// usage of the memory range of `len` bytes from `addr`.
+    ///
+    /// It's similar to madvise(2). The `addr` must be page-aligned.
+    ///
+    /// **Available since kernel 5.6.**
+    ///
+    /// # Safety
+    ///
+    /// Some advices (e.g. [`Advice::DONTNEED`], [`Advice::FREE`]) may discard
+    /// the contents of the memory range, so the caller must ensure that it
+    /// does not break any data that is still in use.
+    ///
+    /// [`Advice::DONTNEED`]: crate::op::Advice::DONTNEED
+    /// [`Advice::FREE`]: crate::op::Advice::FREE
+    #[inline]
+    pub unsafe fn packup_madvise(&mut self, addr: *mut c_void, len: u32, advice: Advice) {
+        self.pack(IORING_OP_MADVISE, -1, addr as _, len, 0);
+        self.0.__bindgen_anon_3.fadvise_advice = i32::from(advice) as _;
+    }
+
     /// Pack up data for the operation that read from a pre-mapped buffer.
     ///
     /// This method must be called after [`register_buffers`] is called and
@@ -118,10 +479,22 @@ impl<'a> Packer<'a> {
     ///
     /// **Available since kernel 5.1.**
     ///
+    /// # Safety
+    ///
+    /// The kernel writes into the `buf` after this method returns. So it must
+    /// be kept registered, and must not be accessed until the operation is
+    /// completed.
+    ///
     /// [`register_buffers`]:method@crate::Registrar::register_buffers
     /// [`Registrar`]:struct@crate::Registrar
     #[inline]
-    pub fn packup_read_fixed(&mut self, fd: RawFd, buf: &mut [u8], offset: u64, buf_index: u16) {
+    pub unsafe fn packup_read_fixed(
+        &mut self,
+        fd: RawFd,
+        buf: &mut [u8],
+        offset: u64,
+        buf_index: u16,
+    ) {
         self.pack(
             IORING_OP_READ_FIXED,
             fd,
@@ -144,10 +517,21 @@ impl<'a> Packer<'a> {
     ///
     /// **Available since kernel 5.1.**
     ///
+    /// # Safety
+    ///
+    /// The kernel reads the `buf` after this method returns. So it must be
+    /// kept registered and unmodified until the operation is completed.
+    ///
     /// [`register_buffers`]:method@crate::Registrar::register_buffers
     /// [`Registrar`]:struct@crate::Registrar
     #[inline]
-    pub fn packup_write_fixed(&mut self, fd: RawFd, buf: &[u8], offset: u64, buf_index: u16) {
+    pub unsafe fn packup_write_fixed(
+        &mut self,
+        fd: RawFd,
+        buf: &[u8],
+        offset: u64,
+        buf_index: u16,
+    ) {
         self.pack(
             IORING_OP_WRITE_FIXED,
             fd,
@@ -181,12 +565,1463 @@ impl<'a> Packer<'a> {
         }
     }
 
+    /// Pack up data for the operation that adds, modifies or removes the
+    /// entry for the file descriptor `fd` in the interest list of the epoll
+    /// instance `epfd`.
+    ///
+    /// It's similar to epoll_ctl(2). The `event` is ignored if the `op` is
+    /// [`EpollOp::Delete`], otherwise it's kept by the io_uring instance until
+    /// the operation is completed.
+    ///
+    /// **Available since kernel 5.6.**
+    ///
+    /// [`EpollOp::Delete`]: crate::op::EpollOp::Delete
     #[inline]
-    fn pack(&mut self, opcode: u8, fd: i32, addr: u64, len: u32, offset: u64) {
-        self.0.opcode = opcode;
-        self.0.fd = fd;
-        self.0.__bindgen_anon_1.off = offset;
-        self.0.__bindgen_anon_2.addr = addr;
-        self.0.len = len;
+    pub fn packup_epoll_ctl(&mut self, epfd: RawFd, op: EpollOp, fd: RawFd, event: EpollEvent) {
+        let event = Box::new(event);
+        self.pack(
+            IORING_OP_EPOLL_CTL,
+            epfd,
+            event.as_ptr() as _,
+            c_int::from(op) as _,
+            fd as _,
+        );
+        self.hold(event);
+    }
+
+    /// Pack up data for the operation that waits for events on the epoll
+    /// instance `epfd`.
+    ///
+    /// It's similar to epoll_wait(2) with no timeout. At most
+    /// `events.capacity()` ready events are filled into the owned `events`
+    /// from its start, and the completion event result is the number of them.
+    /// The `events` is kept by the io_uring instance while the operation is in
+    /// flight, and handed back by [`Cqe::take_buf`] at completion time, whose
+    /// length is set to the number of the ready events.
+    ///
+    /// **Available since kernel 6.15.**
+    ///
+    /// [`Cqe::take_buf`]: method@crate::Cqe::take_buf
+    #[inline]
+    pub fn packup_epoll_wait(&mut self, epfd: RawFd, mut events: Vec<EpollEvent>) {
+        unsafe fn fill(events: &mut (dyn Any + Send), len: usize) {
+            let events = events.downcast_mut::<Vec<EpollEvent>>().unwrap();
+            events.set_len(len.min(events.capacity()));
+        }
+
+        self.pack(
+            IORING_OP_EPOLL_WAIT,
+            epfd,
+            events.as_mut_ptr() as _,
+            events.capacity() as _,
+            0,
+        );
+        self.own(|owned, user_data| owned.insert_filled(user_data, events, fill));
+    }
+
+    /// Pack up data for the operation that expires every `interval`, which
+    /// serves as a periodic tick source without resubmission.
+    ///
+    /// A completion event is generated on each expiration, whose result is
+    /// the `ETIME` error. It expires `count` times, or until it's canceled if
+    /// `count` is zero. Every event except the last one is flagged by
+    /// [`Cqe::has_more`]. Use [`TickCounter`] to count the ticks and the
+    /// missed ones.
+    ///
+    /// The `interval` must be kept alive until the SQE is submitted.
+    ///
+    /// **Available since kernel 6.4.**
+    ///
+    /// [`Cqe::has_more`]: method@crate::Cqe::has_more
+    /// [`TickCounter`]: struct@crate::op::TickCounter
+    #[inline]
+    pub fn packup_periodic_timeout(&mut self, interval: &Timespec, count: u32) {
+        self.pack(IORING_OP_TIMEOUT, -1, interval.as_ptr() as _, 1, count as _);
+        self.0.__bindgen_anon_3.timeout_flags = IORING_TIMEOUT_MULTISHOT;
+    }
+
+    /// Pack up data for the operation that hands all the buffers in the
+    /// `group` to the kernel, so that they can be selected by operations.
+    ///
+    /// See [`BufferGroup`] for details.
+    ///
+    /// **Available since kernel 5.7.**
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the buffers is already provided.
+    ///
+    /// [`BufferGroup`]: struct@crate::op::BufferGroup
+    #[inline]
+    pub fn packup_provide_buffers(&mut self, group: &mut BufferGroup) {
+        let ptr = group.provide(self.2, 0, group.len());
+        self.pack(
+            IORING_OP_PROVIDE_BUFFERS,
+            group.len() as _,
+            ptr as _,
+            group.buf_len() as _,
+            0,
+        );
+        self.0.__bindgen_anon_4.buf_group = group.id();
+    }
+
+    /// Like [`packup_provide_buffers`], but it hands only the buffer
+    /// identified by `bid` to the kernel.
+    ///
+    /// It's used to recycle a buffer once the data selected into it is
+    /// consumed.
+    ///
+    /// **Available since kernel 5.7.**
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bid` is out of the group, the buffer is already provided,
+    /// or the other buffers are provided to another io_uring instance.
+    ///
+    /// [`packup_provide_buffers`]: method@Self::packup_provide_buffers
+    #[inline]
+    pub fn packup_provide_buffer(&mut self, group: &mut BufferGroup, bid: u16) {
+        let ptr = group.provide(self.2, bid, 1);
+        self.pack(
+            IORING_OP_PROVIDE_BUFFERS,
+            1,
+            ptr as _,
+            group.buf_len() as _,
+            bid as _,
+        );
+        self.0.__bindgen_anon_4.buf_group = group.id();
+    }
+
+    /// Pack up data for the operation that takes back up to `nbufs` buffers
+    /// of the `group` from the kernel. The completion event result is the
+    /// number of buffers removed.
+    ///
+    /// **Available since kernel 5.7.**
+    #[inline]
+    pub fn packup_remove_buffers(&mut self, group: &BufferGroup, nbufs: u16) {
+        self.pack(IORING_OP_REMOVE_BUFFERS, nbufs as _, 0, 0, 0);
+        self.0.__bindgen_anon_4.buf_group = group.id();
+    }
+
+    /// Pack up data for the operation that reads from the file descriptor
+    /// `fd` into a buffer selected from the `group`.
+    ///
+    /// It's similar to pread(2). The id of the selected buffer is given by
+    /// [`Cqe::buffer_id`], and the data can be looked up by
+    /// [`BufferGroup::get`] or [`BufRing::get`].
+    ///
+    /// **Available since kernel 5.7.**
+    ///
+    /// [`Cqe::buffer_id`]: method@crate::Cqe::buffer_id
+    /// [`BufferGroup::get`]: method@crate::op::BufferGroup::get
+    /// [`BufRing::get`]: method@crate::BufRing::get
+    #[inline]
+    pub fn packup_read_select(&mut self, fd: RawFd, group: &impl BufSelect, offset: u64) {
+        self.pack(IORING_OP_READ, fd, 0, group.buf_len() as _, offset);
+        self.select_buffer(group);
+    }
+
+    /// Pack up data for the operation that accepts a connection on the
+    /// listening socket `fd`.
+    ///
+    /// It's similar to accept4(2). The completion event result is the file
+    /// descriptor of the accepted socket.
+    ///
+    /// If `addr` is given, it's kept by the io_uring instance while the
+    /// operation is in flight, and handed back by [`Cqe::take_buf`] at
+    /// completion time, filled in with the address of the peer socket.
+    ///
+    /// **Available since kernel 5.5.**
+    ///
+    /// [`Cqe::take_buf`]: method@crate::Cqe::take_buf
+    #[inline]
+    pub fn packup_accept(
+        &mut self,
+        fd: RawFd,
+        addr: Option<Box<SockAddrStorage>>,
+        flags: SockFlags,
+    ) {
+        match addr {
+            Some(mut addr) => {
+                let (ptr, len) = (addr.as_mut_ptr(), addr.len_mut_ptr());
+                self.pack(IORING_OP_ACCEPT, fd, ptr as _, 0, len as _);
+                self.own(|owned, user_data| owned.insert(user_data, addr));
+            }
+            None => self.pack(IORING_OP_ACCEPT, fd, 0, 0, 0),
+        }
+        self.0.__bindgen_anon_3.accept_flags = flags.bits();
+    }
+
+    /// Like [`packup_accept`], but it keeps accepting connections on the
+    /// listening socket `fd` until it's cancelled or fails.
+    ///
+    /// A completion event is posted for each accepted connection, and
+    /// [`Cqe::has_more`] tells whether more events will follow. The peer
+    /// address can't be retrieved by this operation.
+    ///
+    /// **Available since kernel 5.19.**
+    ///
+    /// [`packup_accept`]: method@Self::packup_accept
+    /// [`Cqe::has_more`]: method@crate::Cqe::has_more
+    #[inline]
+    pub fn packup_accept_multi(&mut self, fd: RawFd, flags: SockFlags) {
+        self.packup_accept(fd, None, flags);
+        self.0.ioprio = IORING_ACCEPT_MULTISHOT;
+    }
+
+    /// Like [`packup_accept_multi`], but each accepted socket is installed
+    /// into a free slot of the fixed file table instead of the normal file
+    /// table.
+    ///
+    /// The completion event result is the index of the slot the socket is
+    /// installed into, not a file descriptor, which can be used with
+    /// `IOSQE_FIXED_FILE`. The fixed file table must be registered in advance
+    /// (see [`register_files_sparse`]), and the operation fails with `ENFILE`
+    /// once the table is full. [`SockFlags::CLOEXEC`] is ignored, since the
+    /// kernel rejects it for the fixed file table.
+    ///
+    /// **Available since kernel 5.19.**
+    ///
+    /// [`packup_accept_multi`]: method@Self::packup_accept_multi
+    /// [`register_files_sparse`]: method@crate::Registrar::register_files_sparse
+    /// [`SockFlags::CLOEXEC`]: crate::op::SockFlags::CLOEXEC
+    #[inline]
+    pub fn packup_accept_multi_direct(&mut self, fd: RawFd, flags: SockFlags) {
+        self.packup_accept_multi(fd, flags & !SockFlags::CLOEXEC);
+        self.0.__bindgen_anon_5.file_index = IORING_FILE_INDEX_ALLOC;
+    }
+
+    /// Pack up data for the operation that connects the socket `fd` to the
+    /// address specified by `addr`.
+    ///
+    /// It's similar to connect(2). The `addr` is kept by the io_uring
+    /// instance until the operation is completed.
+    ///
+    /// **Available since kernel 5.5.**
+    #[inline]
+    pub fn packup_connect(&mut self, fd: RawFd, addr: SockAddrStorage) {
+        let addr = Box::new(addr);
+        self.pack(
+            IORING_OP_CONNECT,
+            fd,
+            addr.as_ptr() as _,
+            0,
+            addr.len() as _,
+        );
+        self.hold(addr);
+    }
+
+    /// Pack up data for the operation that creates an endpoint for
+    /// communication.
+    ///
+    /// It's similar to socket(2), where the `flags` are bitwise ORed into the
+    /// `ty`. The completion event result is the file descriptor of the new
+    /// socket.
+    ///
+    /// **Available since kernel 5.19.**
+    #[inline]
+    pub fn packup_socket(
+        &mut self,
+        domain: Domain,
+        ty: SockType,
+        protocol: Protocol,
+        flags: SockFlags,
+    ) {
+        self.pack(
+            IORING_OP_SOCKET,
+            domain.into(),
+            0,
+            i32::from(protocol) as _,
+            (i32::from(ty) as u32 | flags.bits()) as _,
+        );
+    }
+
+    /// Like [`packup_socket`], but the new socket is installed into the fixed
+    /// file table instead of the normal file table.
+    ///
+    /// If `file_index` is `Some`, the socket is installed into the slot, which
+    /// replaces the existing file if any. Otherwise, a free slot is allocated
+    /// and the completion event result is the index of it. The fixed file
+    /// table must be registered in advance (see [`register_files_sparse`]).
+    /// [`SockFlags::CLOEXEC`] is ignored, since the kernel rejects it for the
+    /// fixed file table, which isn't inherited by exec(2) anyway.
+    ///
+    /// **Available since kernel 5.19.**
+    ///
+    /// # Panics
+    ///
+    /// Panics if `file_index` is `u32::MAX - 1` or greater, which can't be
+    /// encoded.
+    ///
+    /// [`packup_socket`]: method@Self::packup_socket
+    /// [`register_files_sparse`]: method@crate::Registrar::register_files_sparse
+    /// [`SockFlags::CLOEXEC`]: crate::op::SockFlags::CLOEXEC
+    #[inline]
+    pub fn packup_socket_direct(
+        &mut self,
+        domain: Domain,
+        ty: SockType,
+        protocol: Protocol,
+        flags: SockFlags,
+        file_index: Option<u32>,
+    ) {
+        self.packup_socket(domain, ty, protocol, flags & !SockFlags::CLOEXEC);
+        self.0.__bindgen_anon_5.file_index = encode_file_index(file_index);
+    }
+
+    /// Pack up data for the operation that assigns the address `addr` to the
+    /// socket `fd`.
+    ///
+    /// It's similar to bind(2). The `addr` is kept by the io_uring instance
+    /// until the operation is completed.
+    ///
+    /// **Available since kernel 6.11.**
+    #[inline]
+    pub fn packup_bind(&mut self, fd: RawFd, addr: SockAddrStorage) {
+        let addr = Box::new(addr);
+        self.pack(IORING_OP_BIND, fd, addr.as_ptr() as _, 0, addr.len() as _);
+        self.hold(addr);
+    }
+
+    /// Pack up data for the operation that marks the socket `fd` as a passive
+    /// socket, which will be used to accept incoming connections.
+    ///
+    /// It's similar to listen(2). The `backlog` defines the maximum length to
+    /// which the queue of pending connections may grow.
+    ///
+    /// **Available since kernel 6.11.**
+    #[inline]
+    pub fn packup_listen(&mut self, fd: RawFd, backlog: u32) {
+        self.pack(IORING_OP_LISTEN, fd, 0, backlog, 0);
+    }
+
+    /// Pack up data for the operation that shuts down all or part of a
+    /// full-duplex connection on the socket `fd`.
+    ///
+    /// It's similar to shutdown(2).
+    ///
+    /// **Available since kernel 5.11.**
+    #[inline]
+    pub fn packup_shutdown(&mut self, fd: RawFd, how: Shutdown) {
+        let how = match how {
+            Shutdown::Read => libc::SHUT_RD,
+            Shutdown::Write => libc::SHUT_WR,
+            Shutdown::Both => libc::SHUT_RDWR,
+        };
+        self.pack(IORING_OP_SHUTDOWN, fd, 0, how as _, 0);
     }
+
+    /// Pack up data for the operation that sends the message in the `buf` on
+    /// the connected socket `fd`.
+    ///
+    /// It's similar to send(2). The `buf` must be kept alive until the
+    /// operation is completed, which is guaranteed by [`Scope::send`]. The
+    /// completion event result is the number of bytes sent.
+    ///
+    /// **Available since kernel 5.6.**
+    ///
+    /// [`Scope::send`]: method@crate::Scope::send
+    #[inline]
+    pub(crate) fn packup_send(&mut self, fd: RawFd, buf: &[u8], flags: MsgFlags) {
+        self.pack(IORING_OP_SEND, fd, buf.as_ptr() as _, buf.len() as _, 0);
+        self.0.__bindgen_anon_3.msg_flags = flags.bits();
+    }
+
+    /// Pack up data for the operation that sends the initialized bytes of the
+    /// owned buffer `buf` on the connected socket `fd`.
+    ///
+    /// It's similar to send(2). The `buf` is kept by the io_uring instance
+    /// while the operation is in flight, and handed back by
+    /// [`Cqe::take_buf`] at completion time. The completion event result is
+    /// the number of bytes sent.
+    ///
+    /// **Available since kernel 5.6.**
+    ///
+    /// [`Cqe::take_buf`]: method@crate::Cqe::take_buf
+    #[inline]
+    pub fn packup_send_owned(&mut self, fd: RawFd, buf: impl IoBuf, flags: MsgFlags) {
+        let (ptr, len) = (buf.stable_ptr(), buf.bytes_init());
+        self.pack(IORING_OP_SEND, fd, ptr as _, len as _, 0);
+        self.0.__bindgen_anon_3.msg_flags = flags.bits();
+        self.own(|owned, user_data| owned.insert(user_data, buf));
+    }
+
+    /// Like [`packup_send_owned`], but the kernel transmits the data directly
+    /// from the `buf`, without copying it.
+    ///
+    /// Since the kernel may use the `buf` even after the data is sent, the
+    /// operation posts two completion events with the same `user_data`. The
+    /// first one reports the result, and if [`Cqe::has_more`] is `true`, the
+    /// second one follows when the kernel is done with the `buf`, for which
+    /// [`Cqe::is_notification`] returns `true`. The `buf` is kept by the
+    /// io_uring instance until then, and handed back by [`Cqe::take_buf`] of
+    /// the last event.
+    ///
+    /// **Available since kernel 6.0.**
+    ///
+    /// [`packup_send_owned`]: method@Self::packup_send_owned
+    /// [`Cqe::has_more`]: method@crate::Cqe::has_more
+    /// [`Cqe::is_notification`]: method@crate::Cqe::is_notification
+    /// [`Cqe::take_buf`]: method@crate::Cqe::take_buf
+    #[inline]
+    pub fn packup_send_zc_owned(&mut self, fd: RawFd, buf: impl IoBuf, flags: MsgFlags) {
+        let (ptr, len) = (buf.stable_ptr(), buf.bytes_init());
+        self.pack(IORING_OP_SEND_ZC, fd, ptr as _, len as _, 0);
+        self.0.__bindgen_anon_3.msg_flags = flags.bits();
+        // The first event is flagged by `IORING_CQE_F_MORE` if the
+        // notification follows, so the `buf` is handed back by the last one.
+        self.own(|owned, user_data| owned.insert(user_data, buf));
+    }
+
+    /// Like [`packup_send_zc_owned`], but the data is sent from the borrowed
+    /// `buf`.
+    ///
+    /// **Available since kernel 6.0.**
+    ///
+    /// # Safety
+    ///
+    /// The kernel reads the `buf` after this method returns, until the
+    /// notification event is posted. So it must be kept alive and untouched
+    /// until then, and it can be reused after that.
+    ///
+    /// [`packup_send_zc_owned`]: method@Self::packup_send_zc_owned
+    #[inline]
+    pub unsafe fn packup_send_zc(&mut self, fd: RawFd, buf: &[u8], flags: MsgFlags) {
+        self.pack(IORING_OP_SEND_ZC, fd, buf.as_ptr() as _, buf.len() as _, 0);
+        self.0.__bindgen_anon_3.msg_flags = flags.bits();
+    }
+
+    /// Like [`packup_send_zc`], but the data is sent from a pre-mapped buffer.
+    ///
+    /// This method must be called after [`register_buffers`] is called and
+    /// succeed. The memory range described by the `buf` must fall within the
+    /// buffer indexed by `buf_index`.
+    ///
+    /// **Available since kernel 6.0.**
+    ///
+    /// # Safety
+    ///
+    /// See [`packup_send_zc`].
+    ///
+    /// [`packup_send_zc`]: method@Self::packup_send_zc
+    /// [`register_buffers`]:method@crate::Registrar::register_buffers
+    #[inline]
+    pub unsafe fn packup_send_zc_fixed(
+        &mut self,
+        fd: RawFd,
+        buf: &[u8],
+        flags: MsgFlags,
+        buf_index: u16,
+    ) {
+        self.packup_send_zc(fd, buf, flags);
+        self.0.ioprio = IORING_RECVSEND_FIXED_BUF;
+        self.0.__bindgen_anon_4.buf_index = buf_index;
+    }
+
+    /// Pack up data for the operation that receives a message from the socket
+    /// `fd` into the `buf`.
+    ///
+    /// It's similar to recv(2). The `buf` must be kept alive until the
+    /// operation is completed, which is guaranteed by [`Scope::recv`]. The
+    /// completion event result is the number of bytes received.
+    ///
+    /// **Available since kernel 5.6.**
+    ///
+    /// [`Scope::recv`]: method@crate::Scope::recv
+    #[inline]
+    pub(crate) fn packup_recv(&mut self, fd: RawFd, buf: &mut [u8], flags: MsgFlags) {
+        self.pack(IORING_OP_RECV, fd, buf.as_mut_ptr() as _, buf.len() as _, 0);
+        self.0.__bindgen_anon_3.msg_flags = flags.bits();
+    }
+
+    /// Pack up data for the operation that receives a message from the socket
+    /// `fd` into the owned buffer `buf`, which is filled from its start.
+    ///
+    /// It's similar to recv(2). The `buf` is kept by the io_uring instance
+    /// while the operation is in flight, and handed back by
+    /// [`Cqe::take_buf`] at completion time, whose initialized bytes cover the
+    /// received data. The completion event result is the number of bytes
+    /// received.
+    ///
+    /// **Available since kernel 5.6.**
+    ///
+    /// [`Cqe::take_buf`]: method@crate::Cqe::take_buf
+    #[inline]
+    pub fn packup_recv_owned(&mut self, fd: RawFd, mut buf: impl IoBufMut, flags: MsgFlags) {
+        let (ptr, len) = (buf.stable_mut_ptr(), buf.bytes_total());
+        self.pack(IORING_OP_RECV, fd, ptr as _, len as _, 0);
+        self.0.__bindgen_anon_3.msg_flags = flags.bits();
+        self.own(|owned, user_data| owned.insert_mut(user_data, buf));
+    }
+
+    /// Like [`packup_recv_owned`], but the message is received into a buffer
+    /// selected from the `group`.
+    ///
+    /// The id of the selected buffer is given by [`Cqe::buffer_id`], and the
+    /// data can be looked up by [`BufferGroup::get`] or [`BufRing::get`].
+    ///
+    /// **Available since kernel 5.7.**
+    ///
+    /// [`packup_recv_owned`]: method@Self::packup_recv_owned
+    /// [`Cqe::buffer_id`]: method@crate::Cqe::buffer_id
+    /// [`BufferGroup::get`]: method@crate::op::BufferGroup::get
+    /// [`BufRing::get`]: method@crate::BufRing::get
+    #[inline]
+    pub fn packup_recv_select(&mut self, fd: RawFd, group: &impl BufSelect, flags: MsgFlags) {
+        self.pack(IORING_OP_RECV, fd, 0, group.buf_len() as _, 0);
+        self.0.__bindgen_anon_3.msg_flags = flags.bits();
+        self.select_buffer(group);
+    }
+
+    /// Like [`packup_recv_select`], but it keeps receiving messages from the
+    /// socket `fd` until it's cancelled or fails.
+    ///
+    /// A completion event is posted for each received message with a buffer
+    /// selected from the `group`, and [`Cqe::has_more`] tells whether more
+    /// events will follow. The operation is terminated when the `group` runs
+    /// out of buffers, so they should be recycled promptly.
+    ///
+    /// **Available since kernel 6.0.**
+    ///
+    /// [`packup_recv_select`]: method@Self::packup_recv_select
+    /// [`Cqe::has_more`]: method@crate::Cqe::has_more
+    #[inline]
+    pub fn packup_recv_multi(&mut self, fd: RawFd, group: &impl BufSelect, flags: MsgFlags) {
+        self.pack(IORING_OP_RECV, fd, 0, 0, 0);
+        self.0.__bindgen_anon_3.msg_flags = flags.bits();
+        self.0.ioprio = IORING_RECV_MULTISHOT;
+        self.select_buffer(group);
+    }
+
+    /// Like [`packup_recv_select`], but the message is received into as many
+    /// buffers selected from the `buf_ring` as it needs, rather than a single
+    /// one.
+    ///
+    /// The buffers are consumed from the ring in order, and they can be
+    /// iterated by [`BufRing::bundle`] once the operation is completed.
+    ///
+    /// **Available since kernel 6.10.**
+    ///
+    /// [`packup_recv_select`]: method@Self::packup_recv_select
+    /// [`BufRing::bundle`]: method@crate::BufRing::bundle
+    #[inline]
+    pub fn packup_recv_bundle(&mut self, fd: RawFd, buf_ring: &BufRing, flags: MsgFlags) {
+        self.pack(IORING_OP_RECV, fd, 0, 0, 0);
+        self.0.__bindgen_anon_3.msg_flags = flags.bits();
+        self.0.ioprio = IORING_RECVSEND_BUNDLE;
+        self.select_buffer(buf_ring);
+    }
+
+    /// Combination of [`packup_recv_multi`] and [`packup_recv_bundle`], which
+    /// keeps receiving messages into as many buffers as each of them needs.
+    ///
+    /// **Available since kernel 6.10.**
+    ///
+    /// [`packup_recv_multi`]: method@Self::packup_recv_multi
+    /// [`packup_recv_bundle`]: method@Self::packup_recv_bundle
+    #[inline]
+    pub fn packup_recv_multi_bundle(&mut self, fd: RawFd, buf_ring: &BufRing, flags: MsgFlags) {
+        self.packup_recv_bundle(fd, buf_ring, flags);
+        self.0.ioprio |= IORING_RECV_MULTISHOT;
+    }
+
+    /// Pack up data for the operation that sends all the data queued in the
+    /// `buf_ring` on the connected socket `fd` at once.
+    ///
+    /// The data is queued by [`BufRing::push_with_len`]. The completion event
+    /// result is the number of bytes sent, and the buffers consumed by the
+    /// operation can be iterated by [`BufRing::bundle`].
+    ///
+    /// **Available since kernel 6.10.**
+    ///
+    /// [`BufRing::push_with_len`]: method@crate::BufRing::push_with_len
+    /// [`BufRing::bundle`]: method@crate::BufRing::bundle
+    #[inline]
+    pub fn packup_send_bundle(&mut self, fd: RawFd, buf_ring: &BufRing, flags: MsgFlags) {
+        self.pack(IORING_OP_SEND, fd, 0, 0, 0);
+        self.0.__bindgen_anon_3.msg_flags = flags.bits();
+        self.0.ioprio = IORING_RECVSEND_BUNDLE;
+        self.select_buffer(buf_ring);
+    }
+
+    /// Pack up data for the operation that sends the message described by
+    /// the `msg` on the socket `fd`.
+    ///
+    /// It's similar to sendmsg(2). The completion event result is the number
+    /// of bytes sent.
+    ///
+    /// **Available since kernel 5.3.**
+    ///
+    /// # Safety
+    ///
+    /// The kernel reads the `msg`, and the buffers, the address and the
+    /// control message described by it, after this method returns. So they
+    /// must be kept alive and unmoved until the operation is completed.
+    #[inline]
+    pub unsafe fn packup_sendmsg(&mut self, fd: RawFd, msg: &MsgHdr<'_>, flags: MsgFlags) {
+        self.pack(IORING_OP_SENDMSG, fd, msg.as_ptr() as _, 1, 0);
+        self.0.__bindgen_anon_3.msg_flags = flags.bits();
+    }
+
+    /// Like [`packup_sendmsg`], but the kernel transmits the data directly
+    /// from the buffers described by the `msg`, without copying them.
+    ///
+    /// As described in [`packup_send_zc_owned`], the notification event
+    /// follows when the kernel is done with the buffers.
+    ///
+    /// **Available since kernel 6.1.**
+    ///
+    /// # Safety
+    ///
+    /// The kernel reads the `msg`, and the buffers, the address and the
+    /// control message described by it, after this method returns. So they
+    /// must be kept alive and unmoved until the operation is completed, and
+    /// the buffers must be kept untouched until the notification event is
+    /// posted.
+    ///
+    /// [`packup_sendmsg`]: method@Self::packup_sendmsg
+    /// [`packup_send_zc_owned`]: method@Self::packup_send_zc_owned
+    #[inline]
+    pub unsafe fn packup_sendmsg_zc(&mut self, fd: RawFd, msg: &MsgHdr<'_>, flags: MsgFlags) {
+        self.pack(IORING_OP_SENDMSG_ZC, fd, msg.as_ptr() as _, 1, 0);
+        self.0.__bindgen_anon_3.msg_flags = flags.bits();
+    }
+
+    /// Pack up data for the operation that receives a message from the socket
+    /// `fd` into the buffers described by the `msg`.
+    ///
+    /// It's similar to recvmsg(2). The kernel updates the `msg` on completion.
+    /// The completion event result is the number of bytes received.
+    ///
+    /// **Available since kernel 5.3.**
+    ///
+    /// # Safety
+    ///
+    /// The kernel writes into the `msg`, and the buffers, the address and the
+    /// control message described by it, after this method returns. So they
+    /// must be kept alive and unmoved, and must not be accessed until the
+    /// operation is completed.
+    #[inline]
+    pub unsafe fn packup_recvmsg(&mut self, fd: RawFd, msg: &mut MsgHdr<'_>, flags: MsgFlags) {
+        self.pack(IORING_OP_RECVMSG, fd, msg.as_mut_ptr() as _, 1, 0);
+        self.0.__bindgen_anon_3.msg_flags = flags.bits();
+    }
+
+    /// Like [`packup_recv_multi`], but it receives the source address and the
+    /// ancillary data as well.
+    ///
+    /// The `msg` must be created by [`MsgHdr::with_reserved`], which reserves
+    /// the space for them in each selected buffer. The message is parsed out
+    /// of the selected buffer by [`RecvMsgOut::parse`]. Each buffer in the
+    /// `group` must be large enough to hold the header and the reserved space,
+    /// otherwise the operation fails with `EFAULT`.
+    ///
+    /// **Available since kernel 6.0.**
+    ///
+    /// # Safety
+    ///
+    /// The kernel reads the `msg` for each received message, so it must be
+    /// kept alive and unmoved until the operation is terminated, which is
+    /// told by [`Cqe::has_more`].
+    ///
+    /// [`packup_recv_multi`]: method@Self::packup_recv_multi
+    /// [`MsgHdr::with_reserved`]: method@crate::op::MsgHdr::with_reserved
+    /// [`RecvMsgOut::parse`]: method@crate::op::RecvMsgOut::parse
+    /// [`Cqe::has_more`]: method@crate::Cqe::has_more
+    #[inline]
+    pub unsafe fn packup_recvmsg_multi(
+        &mut self,
+        fd: RawFd,
+        msg: &MsgHdr<'_>,
+        group: &impl BufSelect,
+        flags: MsgFlags,
+    ) {
+        self.pack(IORING_OP_RECVMSG, fd, msg.as_ptr() as _, 1, 0);
+        self.0.__bindgen_anon_3.msg_flags = flags.bits();
+        self.0.ioprio = IORING_RECV_MULTISHOT;
+        self.select_buffer(group);
+    }
+
+    /// Pack up data for the operation that opens the file specified by `path`
+    /// relative to the directory `dirfd`.
+    ///
+    /// It's similar to openat(2). If `path` is relative and `dirfd` is
+    /// `libc::AT_FDCWD`, it's interpreted relative to the current working
+    /// directory. The `mode` is applied only when a new file is created. The
+    /// completion event result is the new file descriptor. The `path` is kept
+    /// by the io_uring instance until the operation is completed.
+    ///
+    /// **Available since kernel 5.6.**
+    #[inline]
+    pub fn packup_openat(&mut self, dirfd: RawFd, path: CString, flags: OFlag, mode: mode_t) {
+        self.pack(IORING_OP_OPENAT, dirfd, path.as_ptr() as _, mode, 0);
+        self.0.__bindgen_anon_3.open_flags = flags.bits();
+        self.hold(path);
+    }
+
+    /// Like [`packup_openat`], but how to open the file is specified by the
+    /// `how`, which can also restrict how the `path` is resolved.
+    ///
+    /// It's similar to openat2(2). The `path` and `how` are kept by the
+    /// io_uring instance until the operation is completed.
+    ///
+    /// **Available since kernel 5.6.**
+    ///
+    /// [`packup_openat`]: method@Self::packup_openat
+    #[inline]
+    pub fn packup_openat2(&mut self, dirfd: RawFd, path: CString, how: OpenHow) {
+        let how = Box::new(how);
+        self.pack(
+            IORING_OP_OPENAT2,
+            dirfd,
+            path.as_ptr() as _,
+            mem::size_of::<open_how>() as _,
+            how.as_ptr() as _,
+        );
+        self.hold((path, how));
+    }
+
+    /// Pack up data for the operation that closes the file descriptor `fd`.
+    ///
+    /// It's similar to close(2).
+    ///
+    /// **Available since kernel 5.6.**
+    #[inline]
+    pub fn packup_close(&mut self, fd: RawFd) {
+        self.pack(IORING_OP_CLOSE, fd, 0, 0, 0);
+    }
+
+    /// Pack up data for the operation that gets the status of the file
+    /// specified by `path` relative to the directory `dirfd` into the owned
+    /// `statx`.
+    ///
+    /// It's similar to statx(2). The `mask` tells the kernel which fields the
+    /// caller is interested in, and [`Statx::mask`] reports which ones are
+    /// actually filled. The `path` and `statx` are kept by the io_uring
+    /// instance until the operation is completed, and the filled `statx` is
+    /// handed back by [`Cqe::take_buf`].
+    ///
+    /// **Available since kernel 5.6.**
+    ///
+    /// [`Statx::mask`]: method@crate::op::Statx::mask
+    /// [`Cqe::take_buf`]: method@crate::Cqe::take_buf
+    #[inline]
+    pub fn packup_statx(
+        &mut self,
+        dirfd: RawFd,
+        path: CString,
+        flags: AtFlags,
+        mask: StatxMask,
+        mut statx: Box<Statx>,
+    ) {
+        self.pack(
+            IORING_OP_STATX,
+            dirfd,
+            path.as_ptr() as _,
+            mask.bits(),
+            statx.as_mut_ptr() as _,
+        );
+        self.0.__bindgen_anon_3.statx_flags = flags.bits();
+        self.own(|owned, user_data| {
+            let user_data = owned.insert(user_data, statx);
+            owned.hold(user_data, path);
+            user_data
+        });
+    }
+
+    /// Pack up data for the operation that renames the file `oldpath`
+    /// relative to the directory `olddirfd` to `newpath` relative to the
+    /// directory `newdirfd`.
+    ///
+    /// It's similar to renameat2(2). The paths are kept by the io_uring
+    /// instance until the operation is completed.
+    ///
+    /// **Available since kernel 5.11.**
+    #[inline]
+    pub fn packup_renameat(
+        &mut self,
+        olddirfd: RawFd,
+        oldpath: CString,
+        newdirfd: RawFd,
+        newpath: CString,
+        flags: RenameFlags,
+    ) {
+        self.pack(
+            IORING_OP_RENAMEAT,
+            olddirfd,
+            oldpath.as_ptr() as _,
+            newdirfd as _,
+            newpath.as_ptr() as _,
+        );
+        self.0.__bindgen_anon_3.rename_flags = flags.bits();
+        self.hold((oldpath, newpath));
+    }
+
+    /// Pack up data for the operation that removes the `path` relative to the
+    /// directory `dirfd`.
+    ///
+    /// It's similar to unlinkat(2). If the `flags` contains
+    /// [`AtFlags::REMOVEDIR`], the `path` must be an empty directory. The
+    /// `path` is kept by the io_uring instance until the operation is
+    /// completed.
+    ///
+    /// **Available since kernel 5.11.**
+    ///
+    /// [`AtFlags::REMOVEDIR`]: crate::op::AtFlags::REMOVEDIR
+    #[inline]
+    pub fn packup_unlinkat(&mut self, dirfd: RawFd, path: CString, flags: AtFlags) {
+        self.pack(IORING_OP_UNLINKAT, dirfd, path.as_ptr() as _, 0, 0);
+        self.0.__bindgen_anon_3.unlink_flags = flags.bits();
+        self.hold(path);
+    }
+
+    /// Pack up data for the operation that creates the directory `path`
+    /// relative to the directory `dirfd`.
+    ///
+    /// It's similar to mkdirat(2). The `path` is kept by the io_uring instance
+    /// until the operation is completed.
+    ///
+    /// **Available since kernel 5.15.**
+    #[inline]
+    pub fn packup_mkdirat(&mut self, dirfd: RawFd, path: CString, mode: mode_t) {
+        self.pack(IORING_OP_MKDIRAT, dirfd, path.as_ptr() as _, mode, 0);
+        self.hold(path);
+    }
+
+    /// Pack up data for the operation that creates the symbolic link
+    /// `linkpath` relative to the directory `newdirfd`, which contains the
+    /// string `target`.
+    ///
+    /// It's similar to symlinkat(2). The `target` and `linkpath` are kept by
+    /// the io_uring instance until the operation is completed.
+    ///
+    /// **Available since kernel 5.15.**
+    #[inline]
+    pub fn packup_symlinkat(&mut self, target: CString, newdirfd: RawFd, linkpath: CString) {
+        self.pack(
+            IORING_OP_SYMLINKAT,
+            newdirfd,
+            target.as_ptr() as _,
+            0,
+            linkpath.as_ptr() as _,
+        );
+        self.hold((target, linkpath));
+    }
+
+    /// Pack up data for the operation that creates the hard link `newpath`
+    /// relative to the directory `newdirfd` to the existing file `oldpath`
+    /// relative to the directory `olddirfd`.
+    ///
+    /// It's similar to linkat(2). The paths are kept by the io_uring instance
+    /// until the operation is completed.
+    ///
+    /// **Available since kernel 5.15.**
+    #[inline]
+    pub fn packup_linkat(
+        &mut self,
+        olddirfd: RawFd,
+        oldpath: CString,
+        newdirfd: RawFd,
+        newpath: CString,
+        flags: AtFlags,
+    ) {
+        self.pack(
+            IORING_OP_LINKAT,
+            olddirfd,
+            oldpath.as_ptr() as _,
+            newdirfd as _,
+            newpath.as_ptr() as _,
+        );
+        self.0.__bindgen_anon_3.hardlink_flags = flags.bits();
+        self.hold((oldpath, newpath));
+    }
+
+    /// Pack up data for the operation that moves `len` bytes of data from the
+    /// file `fd_in` to the file `fd_out`, where one of them must be a pipe.
+    ///
+    /// It's similar to splice(2). The `fd_in` may be either a normal file
+    /// descriptor or an index into the fixed file table, and the `fd_out` can
+    /// also be the index by setting `IOSQE_FIXED_FILE` via [`flags`]. For a
+    /// pipe the offset must be `None`, otherwise the data is read from or
+    /// written to the offset, and `None` means the current file position. The
+    /// completion event result is the number of bytes moved.
+    ///
+    /// **Available since kernel 5.7.**
+    ///
+    /// [`flags`]: method@Self::flags
+    #[inline]
+    pub fn packup_splice(
+        &mut self,
+        fd_in: impl Into<Fd>,
+        off_in: Option<u64>,
+        fd_out: RawFd,
+        off_out: Option<u64>,
+        len: u32,
+        flags: SpliceFlags,
+    ) {
+        self.pack(
+            IORING_OP_SPLICE,
+            fd_out,
+            0,
+            len,
+            off_out.unwrap_or(u64::MAX),
+        );
+        self.0.__bindgen_anon_2.splice_off_in = off_in.unwrap_or(u64::MAX);
+        self.splice_fd_in(fd_in.into(), flags);
+    }
+
+    /// Pack up data for the operation that duplicates `len` bytes of data
+    /// from the pipe `fd_in` to the pipe `fd_out` without consuming it.
+    ///
+    /// It's similar to tee(2). Like [`packup_splice`], the `fd_in` may be
+    /// either a normal file descriptor or an index into the fixed file table.
+    /// The completion event result is the number of bytes duplicated.
+    ///
+    /// **Available since kernel 5.8.**
+    ///
+    /// [`packup_splice`]: method@Self::packup_splice
+    #[inline]
+    pub fn packup_tee(
+        &mut self,
+        fd_in: impl Into<Fd>,
+        fd_out: RawFd,
+        len: u32,
+        flags: SpliceFlags,
+    ) {
+        self.pack(IORING_OP_TEE, fd_out, 0, len, 0);
+        self.splice_fd_in(fd_in.into(), flags);
+    }
+
+    /// Pack up data for the operation that sets the value of the extended
+    /// attribute `name` of the file `path` to the initialized bytes of the
+    /// owned buffer `value`.
+    ///
+    /// It's similar to setxattr(2). By default, the attribute is created if
+    /// it does not exist, or the value is replaced otherwise. The `path`,
+    /// `name` and `value` are kept by the io_uring instance until the
+    /// operation is completed, and the `value` is handed back by
+    /// [`Cqe::take_buf`].
+    ///
+    /// **Available since kernel 5.19.**
+    ///
+    /// [`Cqe::take_buf`]: method@crate::Cqe::take_buf
+    #[inline]
+    pub fn packup_setxattr(
+        &mut self,
+        path: CString,
+        name: CString,
+        value: impl IoBuf,
+        flags: XattrFlags,
+    ) {
+        self.pack(
+            IORING_OP_SETXATTR,
+            0,
+            name.as_ptr() as _,
+            value.bytes_init() as _,
+            value.stable_ptr() as _,
+        );
+        self.0.__bindgen_anon_3.xattr_flags = flags.bits();
+        self.addr3(path.as_ptr() as _);
+        self.own(|owned, user_data| {
+            let user_data = owned.insert(user_data, value);
+            owned.hold(user_data, (path, name));
+            user_data
+        });
+    }
+
+    /// Like [`packup_setxattr`], but sets the extended attribute of the open
+    /// file `fd`.
+    ///
+    /// **Available since kernel 5.19.**
+    ///
+    /// [`packup_setxattr`]: method@Self::packup_setxattr
+    #[inline]
+    pub fn packup_fsetxattr(
+        &mut self,
+        fd: RawFd,
+        name: CString,
+        value: impl IoBuf,
+        flags: XattrFlags,
+    ) {
+        self.pack(
+            IORING_OP_FSETXATTR,
+            fd,
+            name.as_ptr() as _,
+            value.bytes_init() as _,
+            value.stable_ptr() as _,
+        );
+        self.0.__bindgen_anon_3.xattr_flags = flags.bits();
+        self.own(|owned, user_data| {
+            let user_data = owned.insert(user_data, value);
+            owned.hold(user_data, name);
+            user_data
+        });
+    }
+
+    /// Pack up data for the operation that gets the value of the extended
+    /// attribute `name` of the file `path` into the owned buffer `value`.
+    ///
+    /// It's similar to getxattr(2). The completion event result is the size of
+    /// the value. If the `value` has no room, the size is returned without
+    /// retrieving the value, which can be used to determine the size of the
+    /// buffer to be supplied. The `path`, `name` and `value` are kept by the
+    /// io_uring instance until the operation is completed, and the filled
+    /// `value` is handed back by [`Cqe::take_buf`].
+    ///
+    /// **Available since kernel 5.19.**
+    ///
+    /// [`Cqe::take_buf`]: method@crate::Cqe::take_buf
+    #[inline]
+    pub fn packup_getxattr(&mut self, path: CString, name: CString, mut value: impl IoBufMut) {
+        self.pack(
+            IORING_OP_GETXATTR,
+            0,
+            name.as_ptr() as _,
+            value.bytes_total() as _,
+            value.stable_mut_ptr() as _,
+        );
+        self.addr3(path.as_ptr() as _);
+        self.own(|owned, user_data| {
+            let user_data = owned.insert_mut(user_data, value);
+            owned.hold(user_data, (path, name));
+            user_data
+        });
+    }
+
+    /// Like [`packup_getxattr`], but gets the extended attribute of the open
+    /// file `fd`.
+    ///
+    /// **Available since kernel 5.19.**
+    ///
+    /// [`packup_getxattr`]: method@Self::packup_getxattr
+    #[inline]
+    pub fn packup_fgetxattr(&mut self, fd: RawFd, name: CString, mut value: impl IoBufMut) {
+        self.pack(
+            IORING_OP_FGETXATTR,
+            fd,
+            name.as_ptr() as _,
+            value.bytes_total() as _,
+            value.stable_mut_ptr() as _,
+        );
+        self.own(|owned, user_data| {
+            let user_data = owned.insert_mut(user_data, value);
+            owned.hold(user_data, name);
+            user_data
+        });
+    }
+
+    /// Pack up data for the operation that waits on the `futex` as long as
+    /// its value is `expected`.
+    ///
+    /// It's similar to futex_wait(2). The operation completes with 0 when
+    /// it's woken up by a waker whose bitmask intersects the `mask` (use
+    /// [`Futex::MATCH_ANY`] to match any waker), or fails with `EAGAIN` if the
+    /// value is not `expected`. The `futex` is kept by the io_uring instance
+    /// until the operation is completed.
+    ///
+    /// **Available since kernel 6.7.**
+    ///
+    /// [`Futex::MATCH_ANY`]: crate::op::Futex::MATCH_ANY
+    #[inline]
+    pub fn packup_futex_wait(
+        &mut self,
+        futex: Arc<Futex>,
+        expected: u32,
+        mask: u32,
+        flags: FutexFlags,
+    ) {
+        self.pack(
+            IORING_OP_FUTEX_WAIT,
+            (flags.bits() | libc::FUTEX2_SIZE_U32 as u32) as _,
+            futex.as_ptr() as _,
+            0,
+            expected as _,
+        );
+        self.addr3(mask as _);
+        self.hold(futex);
+    }
+
+    /// Pack up data for the operation that wakes at most `nr` waiters of the
+    /// `futex` whose bitmask intersects the `mask`.
+    ///
+    /// It's similar to futex_wake(2). The completion event result is the
+    /// number of woken waiters. The `futex` is kept by the io_uring instance
+    /// until the operation is completed.
+    ///
+    /// **Available since kernel 6.7.**
+    #[inline]
+    pub fn packup_futex_wake(&mut self, futex: Arc<Futex>, nr: u32, mask: u32, flags: FutexFlags) {
+        self.pack(
+            IORING_OP_FUTEX_WAKE,
+            (flags.bits() | libc::FUTEX2_SIZE_U32 as u32) as _,
+            futex.as_ptr() as _,
+            0,
+            nr as _,
+        );
+        self.addr3(mask as _);
+        self.hold(futex);
+    }
+
+    /// Pack up data for the operation that waits on multiple futexes at once.
+    ///
+    /// It's similar to futex_waitv(2). The operation completes when any of
+    /// the `futexes` is woken up, and the completion event result is the
+    /// index of it. The `futexes` and the futexes they refer to are kept by
+    /// the io_uring instance until the operation is completed.
+    ///
+    /// **Available since kernel 6.7.**
+    #[inline]
+    pub fn packup_futex_waitv(&mut self, futexes: Vec<FutexWaitV>) {
+        let waitv: Box<[futex_waitv]> = futexes.iter().map(FutexWaitV::as_raw).collect();
+        self.pack(
+            IORING_OP_FUTEX_WAITV,
+            0,
+            waitv.as_ptr() as _,
+            waitv.len() as _,
+            0,
+        );
+        self.hold((waitv, futexes));
+    }
+
+    /// Pack up data for the operation that waits for a state change of the
+    /// children specified by the `id`.
+    ///
+    /// It's similar to waitid(2). The operation completes with 0 when the
+    /// state of a child changes, and the information about the child is
+    /// filled into the owned `info`, where [`WaitInfo::status`] returns the
+    /// typed status. The `info` is kept by the io_uring instance while the
+    /// operation is in flight, and handed back by [`Cqe::take_buf`] at
+    /// completion time.
+    ///
+    /// **Available since kernel 6.7.**
+    ///
+    /// [`WaitInfo::status`]: method@crate::op::WaitInfo::status
+    /// [`Cqe::take_buf`]: method@crate::Cqe::take_buf
+    #[inline]
+    pub fn packup_waitid(&mut self, id: WaitId, options: WaitOptions, mut info: Box<WaitInfo>) {
+        let (idtype, id) = id.to_raw();
+        self.pack(IORING_OP_WAITID, id, 0, idtype, info.as_mut_ptr() as _);
+        self.0.__bindgen_anon_5.file_index = options.bits();
+        self.own(|owned, user_data| owned.insert(user_data, info));
+    }
+
+    /// Pack up data for the operation that issues the command `cmd_op` to the
+    /// file `fd`, which is passed through to the driver of the file.
+    ///
+    /// The meaning of the `cmd_op` and the payload `cmd` depends on the file.
+    /// The `cmd` is copied into the command area of the SQE, which is 16
+    /// bytes long, or 80 bytes long if the SQ is set up with
+    /// [`Builder::big_sqe`].
+    ///
+    /// **Available since kernel 5.19.**
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `cmd` is longer than the command area.
+    ///
+    /// [`Builder::big_sqe`]: method@crate::Builder::big_sqe
+    #[inline]
+    pub fn packup_uring_cmd(&mut self, fd: RawFd, cmd_op: u32, cmd: &[u8]) {
+        self.pack(IORING_OP_URING_CMD, fd, 0, 0, 0);
+        self.0.__bindgen_anon_1.__bindgen_anon_1.cmd_op = cmd_op;
+        self.0.__bindgen_anon_3.uring_cmd_flags = 0;
+        self.pack_cmd(cmd);
+    }
+
+    /// Pack up data for the operation that gets the number of bytes in the
+    /// receive queue of the socket `fd`, which is the completion event result.
+    ///
+    /// It's similar to the `SIOCINQ` ioctl(2).
+    ///
+    /// **Available since kernel 6.7.**
+    #[inline]
+    pub fn packup_siocinq(&mut self, fd: RawFd) {
+        self.packup_uring_cmd(fd, SOCKET_URING_OP_SIOCINQ, &[]);
+    }
+
+    /// Pack up data for the operation that gets the number of bytes in the
+    /// send queue of the socket `fd`, which is the completion event result.
+    ///
+    /// It's similar to the `SIOCOUTQ` ioctl(2).
+    ///
+    /// **Available since kernel 6.7.**
+    #[inline]
+    pub fn packup_siocoutq(&mut self, fd: RawFd) {
+        self.packup_uring_cmd(fd, SOCKET_URING_OP_SIOCOUTQ, &[]);
+    }
+
+    /// Pack up data for the operation that gets the value of the option
+    /// `optname` at the protocol `level` of the socket `fd` into the owned
+    /// buffer `optval`.
+    ///
+    /// It's similar to getsockopt(2), but only `SOL_SOCKET` is supported as
+    /// the `level`. The completion event result is the actual size of the
+    /// value. The `optval` is kept by the io_uring instance while the
+    /// operation is in flight, and handed back by [`Cqe::take_buf`] at
+    /// completion time.
+    ///
+    /// **Available since kernel 6.7.**
+    ///
+    /// [`Cqe::take_buf`]: method@crate::Cqe::take_buf
+    #[inline]
+    pub fn packup_getsockopt(
+        &mut self,
+        fd: RawFd,
+        level: c_int,
+        optname: c_int,
+        mut optval: impl IoBufMut,
+    ) {
+        self.packup_uring_cmd(fd, SOCKET_URING_OP_GETSOCKOPT, &[]);
+        let (ptr, len) = (optval.stable_mut_ptr(), optval.bytes_total());
+        self.sockopt(level, optname, ptr as _, len);
+        self.own(|owned, user_data| owned.insert_mut(user_data, optval));
+    }
+
+    /// Pack up data for the operation that sets the value of the option
+    /// `optname` at the protocol `level` of the socket `fd` to the
+    /// initialized bytes of the owned buffer `optval`.
+    ///
+    /// It's similar to setsockopt(2). The `optval` is kept by the io_uring
+    /// instance while the operation is in flight, and handed back by
+    /// [`Cqe::take_buf`] at completion time.
+    ///
+    /// **Available since kernel 6.7.**
+    ///
+    /// [`Cqe::take_buf`]: method@crate::Cqe::take_buf
+    #[inline]
+    pub fn packup_setsockopt(
+        &mut self,
+        fd: RawFd,
+        level: c_int,
+        optname: c_int,
+        optval: impl IoBuf,
+    ) {
+        self.packup_uring_cmd(fd, SOCKET_URING_OP_SETSOCKOPT, &[]);
+        let (ptr, len) = (optval.stable_ptr(), optval.bytes_init());
+        self.sockopt(level, optname, ptr as _, len);
+        self.own(|owned, user_data| owned.insert(user_data, optval));
+    }
+
+    /// Pack up data for the operation that posts a completion event to the
+    /// `target` io_uring instance.
+    ///
+    /// The posted completion event has the `res` as the result and the
+    /// `user_data`, which can be used to pass a message or wake up the peer
+    /// waiting on the `target`. The completion event result of the operation
+    /// itself is 0 on success.
+    ///
+    /// **Available since kernel 5.18.**
+    ///
+    /// # Panics
+    ///
+    /// Panics if the most significant bit of `user_data` is set, which is
+    /// reserved (see [`user_data`]).
+    ///
+    /// [`user_data`]: method@Self::user_data
+    #[inline]
+    pub fn packup_msg_ring(&mut self, target: &Uring, res: i32, user_data: u64) {
+        assert_user_data(user_data);
+        self.pack(
+            IORING_OP_MSG_RING,
+            target.as_raw_fd(),
+            IORING_MSG_DATA,
+            res as _,
+            user_data,
+        );
+    }
+
+    /// Pack up data for the operation that installs the fixed file at
+    /// `src_index` into the fixed file table of the `target` io_uring
+    /// instance.
+    ///
+    /// If `dst_index` is `Some`, the file is installed into the slot, which
+    /// replaces the existing file if any. Otherwise, a free slot is allocated.
+    /// Then a completion event is posted to the `target` with the `user_data`,
+    /// whose result is the index of the allocated slot, or 0 if the slot is
+    /// given. The fixed file tables of both io_uring instances must be
+    /// registered in advance (see [`register_files_sparse`]).
+    ///
+    /// **Available since kernel 6.0.**
+    ///
+    /// # Panics
+    ///
+    /// Panics if the most significant bit of `user_data` is set, which is
+    /// reserved (see [`user_data`]), or if `dst_index` is `u32::MAX - 1` or
+    /// greater, which can't be encoded.
+    ///
+    /// [`register_files_sparse`]: method@crate::Registrar::register_files_sparse
+    /// [`user_data`]: method@Self::user_data
+    #[inline]
+    pub fn packup_msg_ring_send_fd(
+        &mut self,
+        target: &Uring,
+        src_index: u32,
+        dst_index: Option<u32>,
+        user_data: u64,
+    ) {
+        assert_user_data(user_data);
+        self.pack(
+            IORING_OP_MSG_RING,
+            target.as_raw_fd(),
+            IORING_MSG_SEND_FD,
+            0,
+            user_data,
+        );
+        self.addr3(src_index as _);
+        self.0.__bindgen_anon_5.file_index = encode_file_index(dst_index);
+    }
+
+    #[inline]
+    fn select_buffer(&mut self, group: &impl BufSelect) {
+        self.require_flags(IOSQE_BUFFER_SELECT);
+        self.0.__bindgen_anon_4.buf_group = group.group_id();
+        // It's tracked, so that its events tell which group the buffer is
+        // selected from.
+        self.own(Registry::insert_tracked);
+    }
+
+    #[inline]
+    fn splice_fd_in(&mut self, fd_in: Fd, flags: SpliceFlags) {
+        let (fd_in, flags) = match fd_in {
+            Fd::Raw(fd) => (fd, flags.bits()),
+            Fd::Fixed(idx) => (idx as _, flags.bits() | SPLICE_F_FD_IN_FIXED),
+        };
+        self.0.__bindgen_anon_5.splice_fd_in = fd_in;
+        self.0.__bindgen_anon_3.splice_flags = flags;
+    }
+
+    #[inline]
+    fn sockopt(&mut self, level: c_int, optname: c_int, optval: u64, optlen: usize) {
+        self.0.__bindgen_anon_2.__bindgen_anon_1.level = level as _;
+        self.0.__bindgen_anon_2.__bindgen_anon_1.optname = optname as _;
+        self.0.__bindgen_anon_5.optlen = optlen as _;
+        self.addr3(optval);
+    }
+
+    // `addr3`, which shares the space with the padding.
+    #[inline]
+    fn addr3(&mut self, v: u64) {
+        self.0.__pad2[0] = v;
+    }
+
+    /// Copy the `cmd` into the command area of the SQE for
+    /// [`packup_uring_cmd`], and zero-fill the rest of the area.
+    ///
+    /// [`packup_uring_cmd`]: method@Self::packup_uring_cmd
+    #[inline]
+    fn pack_cmd(&mut self, cmd: &[u8]) {
+        let pad = &mut self.0.__pad2;
+        let area = unsafe {
+            slice::from_raw_parts_mut(pad.as_mut_ptr() as *mut u8, mem::size_of_val(pad))
+        };
+        let ext_len = self.1.as_ref().map_or(0, |ext| ext.len());
+        assert!(
+            cmd.len() <= area.len() + ext_len,
+            "command is too long for the command area"
+        );
+
+        // The command area continues on the extension of the big SQE.
+        let (head, tail) = cmd.split_at(cmd.len().min(area.len()));
+        area[..head.len()].copy_from_slice(head);
+        area[head.len()..].fill(0);
+        if let Some(ext) = self.1.as_mut() {
+            ext[..tail.len()].copy_from_slice(tail);
+            ext[tail.len()..].fill(0);
+        }
+    }
+
+    /// Track the operation until its completion event is reaped. Returns the
+    /// `user_data` which identifies the operation.
+    #[inline]
+    pub(crate) fn track(&mut self) -> u64 {
+        self.own(Registry::insert_tracked);
+        self.0.user_data
+    }
+
+    /// Pack up data for the internal operation that cancels the tracked
+    /// operation identified by `owned`. It's tracked as well, and returns the
+    /// `user_data` which identifies it.
+    #[inline]
+    pub(crate) fn packup_cancel_tracked(&mut self, owned: u64) -> u64 {
+        self.pack(IORING_OP_ASYNC_CANCEL, -1, owned, 0, 0);
+        self.own(|registry, _| registry.insert_tracked(INTERNAL_USER_DATA));
+        self.0.user_data
+    }
+
+    /// Hand the `data` read by the operation to the io_uring instance, which
+    /// drops it once the operation is completed.
+    #[inline]
+    pub(crate) fn hold<T: Send + 'static>(&mut self, data: T) {
+        self.own(|owned, user_data| owned.insert_held(user_data, data));
+    }
+
+    /// Hand the buffer to the io_uring instance by `insert`, and replace the
+    /// `user_data` with the one which identifies the buffer.
+    #[inline]
+    fn own(&mut self, insert: impl FnOnce(&mut Registry, u64) -> u64) {
+        assert_skip_success(self.0.flags);
+        let mut owned = self.2.owned();
+        let mut user_data = self.0.user_data;
+        if user_data & OWNED_USER_DATA != 0 {
+            // It's packed up again before the submission, so the previous
+            // buffer is not used by the kernel.
+            user_data = owned.complete(user_data, -1).map_or(0, |(data, _)| data);
+        }
+        self.0.user_data = insert(&mut owned, user_data);
+        if self.3 & IOSQE_BUFFER_SELECT != 0 {
+            owned.select(self.0.user_data, unsafe {
+                self.0.__bindgen_anon_4.buf_group
+            });
+        }
+    }
+
+    #[inline]
+    fn require_flags(&mut self, flags: u8) {
+        self.0.flags |= flags;
+        self.3 |= flags;
+    }
+
+    #[inline]
+    fn pack(&mut self, opcode: u8, fd: i32, addr: u64, len: u32, offset: u64) {
+        // The flags required by the operation packed up before are dropped.
+        self.0.flags &= !self.3;
+        self.3 = 0;
+        self.0.opcode = opcode;
+        self.0.fd = fd;
+        self.0.__bindgen_anon_1.off = offset;
+        self.0.__bindgen_anon_2.addr = addr;
+        self.0.len = len;
+    }
+}
+
+// The slot is encoded as the index plus one, since zero means the normal file
+// table, and `IORING_FILE_INDEX_ALLOC` allocates a free slot.
+#[inline]
+fn encode_file_index(index: Option<u32>) -> u32 {
+    match index {
+        Some(idx) => {
+            assert!(
+                idx < IORING_FILE_INDEX_ALLOC - 1,
+                "the fixed file index is out of range"
+            );
+            idx + 1
+        }
+        None => IORING_FILE_INDEX_ALLOC,
+    }
+}
+
+#[inline]
+fn assert_user_data(user_data: u64) {
+    assert!(
+        user_data & OWNED_USER_DATA == 0,
+        "the most significant bit of user_data is reserved"
+    );
+}
+
+#[inline]
+fn assert_skip_success(flags: u8) {
+    assert!(
+        flags & IOSQE_CQE_SKIP_SUCCESS == 0,
+        "IOSQE_CQE_SKIP_SUCCESS can't be set for the owned operation"
+    );
 }
@@ -0,0 +1,103 @@
+use std::time::{Duration, Instant};
+
+use crate::{sys::__kernel_timespec, Cqe};
+
+/// A time interval, which is used by timeout operations such as
+/// [`packup_periodic_timeout`].
+///
+/// [`packup_periodic_timeout`]: method@crate::Packer::packup_periodic_timeout
+#[derive(Debug, Default, Clone, Copy)]
+#[repr(transparent)]
+pub struct Timespec(__kernel_timespec);
+
+impl Timespec {
+    /// Create a new [`Timespec`] of `sec` seconds and `nsec` nanoseconds.
+    pub const fn new(sec: u64, nsec: u32) -> Self {
+        Self(__kernel_timespec {
+            tv_sec: sec as _,
+            tv_nsec: nsec as _,
+        })
+    }
+
+    #[inline]
+    pub(crate) fn as_ptr(&self) -> *const __kernel_timespec {
+        &self.0
+    }
+}
+
+impl From<Duration> for Timespec {
+    #[inline]
+    fn from(duration: Duration) -> Self {
+        Self::new(duration.as_secs(), duration.subsec_nanos())
+    }
+}
+
+impl From<Timespec> for Duration {
+    #[inline]
+    fn from(ts: Timespec) -> Self {
+        Duration::new(ts.0.tv_sec as _, ts.0.tv_nsec as _)
+    }
+}
+
+/// Counter of the ticks of [`packup_periodic_timeout`], which tells how many
+/// ticks are missed by comparing them with the elapsed time.
+///
+/// A tick is missed if it's due by now, but its event hasn't been observed.
+/// It happens when the timer drifts, since the kernel rearms it after each
+/// expiration, or when the consumer lags behind and the events are still
+/// pending in the CQ.
+///
+/// [`packup_periodic_timeout`]: method@crate::Packer::packup_periodic_timeout
+#[derive(Debug)]
+pub struct TickCounter {
+    interval: Duration,
+    start: Instant,
+    ticks: u64,
+    missed: u64,
+}
+
+impl TickCounter {
+    /// Create a new [`TickCounter`] of the periodic timeout of `interval`,
+    /// which starts counting from now. It should be created right before the
+    /// timeout is submitted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `interval` is zero.
+    pub fn new(interval: Duration) -> Self {
+        assert!(!interval.is_zero(), "interval must not be zero");
+        Self {
+            interval,
+            start: Instant::now(),
+            ticks: 0,
+            missed: 0,
+        }
+    }
+
+    /// Count the event `cqe` as a tick. Returns the number of missed ticks at
+    /// the moment, or `None` if the `cqe` is not a tick (e.g. the timeout is
+    /// canceled).
+    pub fn tick(&mut self, cqe: &Cqe) -> Option<u64> {
+        match cqe.result() {
+            Err(err) if err.raw_os_error() == Some(libc::ETIME) => {
+                self.ticks += 1;
+                let due = self.start.elapsed().as_nanos() / self.interval.as_nanos();
+                self.missed = (due as u64).saturating_sub(self.ticks);
+                Some(self.missed)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the number of ticks counted so far.
+    #[inline]
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    /// Returns the number of missed ticks as of the last tick.
+    #[inline]
+    pub fn missed(&self) -> u64 {
+        self.missed
+    }
+}
@@ -0,0 +1,109 @@
+use std::{mem, os::unix::io::RawFd};
+
+use libc::{idtype_t, pid_t, siginfo_t, uid_t};
+
+/// The children to wait for by [`packup_waitid`].
+///
+/// [`packup_waitid`]: method@crate::Packer::packup_waitid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WaitId {
+    /// Wait for any child.
+    All,
+    /// Wait for the child whose process ID matches.
+    Pid(pid_t),
+    /// Wait for any child whose process group ID matches.
+    Pgid(pid_t),
+    /// Wait for the child referred to by the PID file descriptor.
+    PidFd(RawFd),
+}
+
+impl WaitId {
+    #[inline]
+    pub(crate) fn to_raw(self) -> (idtype_t, i32) {
+        match self {
+            WaitId::All => (libc::P_ALL, 0),
+            WaitId::Pid(pid) => (libc::P_PID, pid),
+            WaitId::Pgid(pgid) => (libc::P_PGID, pgid),
+            WaitId::PidFd(fd) => (libc::P_PIDFD, fd),
+        }
+    }
+}
+
+/// A state change of a child.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChildStatus {
+    /// The child terminated normally with the exit status.
+    Exited(i32),
+    /// The child was killed by the signal.
+    Killed(i32),
+    /// The child was killed by the signal and dumped core.
+    Dumped(i32),
+    /// The child was stopped by the signal.
+    Stopped(i32),
+    /// The child was trapped by the signal.
+    Trapped(i32),
+    /// The child was continued by `SIGCONT`.
+    Continued,
+}
+
+/// A caller-owned storage of the child status information filled by
+/// [`packup_waitid`].
+///
+/// [`packup_waitid`]: method@crate::Packer::packup_waitid
+#[derive(Clone, Copy)]
+pub struct WaitInfo(siginfo_t);
+
+impl WaitInfo {
+    /// Create a new zeroed [`WaitInfo`].
+    pub fn new() -> Self {
+        Self(unsafe { mem::zeroed() })
+    }
+
+    /// Returns the process ID of the child, or 0 if no child has changed the
+    /// state (only possible with [`WaitOptions::NOHANG`]).
+    ///
+    /// [`WaitOptions::NOHANG`]: crate::op::WaitOptions::NOHANG
+    #[inline]
+    pub fn pid(&self) -> pid_t {
+        unsafe { self.0.si_pid() }
+    }
+
+    /// Returns the real user ID of the child.
+    #[inline]
+    pub fn uid(&self) -> uid_t {
+        unsafe { self.0.si_uid() }
+    }
+
+    /// Returns the state change of the child, or `None` if no child has
+    /// changed the state.
+    pub fn status(&self) -> Option<ChildStatus> {
+        if self.pid() == 0 {
+            return None;
+        }
+        let status = unsafe { self.0.si_status() };
+        match self.0.si_code {
+            libc::CLD_EXITED => Some(ChildStatus::Exited(status)),
+            libc::CLD_KILLED => Some(ChildStatus::Killed(status)),
+            libc::CLD_DUMPED => Some(ChildStatus::Dumped(status)),
+            libc::CLD_STOPPED => Some(ChildStatus::Stopped(status)),
+            libc::CLD_TRAPPED => Some(ChildStatus::Trapped(status)),
+            libc::CLD_CONTINUED => Some(ChildStatus::Continued),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn as_mut_ptr(&mut self) -> *mut siginfo_t {
+        &mut self.0
+    }
+}
+
+// The pointers in the `siginfo_t` (e.g. the faulting address) are only
+// values reported by the kernel, so it can be sent between threads.
+unsafe impl Send for WaitInfo {}
+
+impl Default for WaitInfo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,10 +1,15 @@
-use std::sync::{
-    atomic::{AtomicU32, Ordering},
-    Arc,
+use std::{
+    collections::VecDeque,
+    mem,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
 };
 
 use crate::{
-    sys::{io_cqring_offsets, io_uring_cqe},
+    op::{INTERNAL_USER_DATA, OWNED_USER_DATA},
+    sys::{io_cqring_offsets, io_uring_cqe, IORING_CQE_F_MORE},
     Cqe, Uring,
 };
 
@@ -18,10 +23,15 @@ pub struct Cq {
     tail: *const AtomicU32,
     ring_mask: *const u32,
     ring_entries: *const u32,
+    #[allow(dead_code)]
     flags: Option<*const AtomicU32>,
+    #[allow(dead_code)]
     overflow: *const AtomicU32,
     cqes: *const io_uring_cqe,
-    ring: Arc<Mmap>,
+    _ring: Arc<Mmap>,
+
+    // Events which are moved out of the ring, but not reaped yet.
+    backlog: VecDeque<Cqe>,
 }
 
 impl Cq {
@@ -41,7 +51,9 @@ impl Cq {
                 },
                 overflow: ring.add(offset.overflow as _) as _,
                 cqes: ring.add(offset.cqes as _) as _,
-                ring,
+                _ring: ring,
+
+                backlog: VecDeque::new(),
             }
         }
     }
@@ -71,7 +83,7 @@ impl Cq {
     ///
     /// [`reap_cqe`]: method@Self::reap_cqe
     #[inline]
-    pub fn reap_cqes(&mut self, want: usize) -> Result<Reaper, &'static str> {
+    pub fn reap_cqes(&mut self, want: usize) -> Result<Reaper<'_>, &'static str> {
         if self.len() < want {
             return Err("Failed to get cqes as much as you want");
         }
@@ -88,6 +100,35 @@ impl Cq {
     /// Returns the number of events in the CQ.
     #[inline]
     pub fn len(&self) -> usize {
+        self.ring_len() + self.backlog.len()
+    }
+
+    /// Returns `true` if the CQ contains no events.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Move all events in the ring to the backlog, so that the ring is
+    /// vacated and the tracked operations are accounted for. The events of
+    /// the internal operations are discarded.
+    pub(crate) fn stash(&mut self) {
+        let mut backlog = mem::take(&mut self.backlog);
+        let len = self.ring_len();
+        backlog.extend(
+            Reaper::new(self, len as _).filter(|cqe| cqe.user_data() != INTERNAL_USER_DATA),
+        );
+        self.backlog = backlog;
+    }
+
+    /// Returns `true` if the `uring` is the one which the CQ belongs to.
+    #[inline]
+    pub(crate) fn belongs_to(&self, uring: &Arc<Uring>) -> bool {
+        Arc::ptr_eq(&self.uring, uring)
+    }
+
+    #[inline]
+    fn ring_len(&self) -> usize {
         (unsafe {
             let tail = (*self.tail).load(Ordering::Acquire);
             let head = *(self.head as *const u32);
@@ -103,11 +144,18 @@ pub struct Reaper<'a> {
     cq: &'a mut Cq,
     len: u32,
     reaped: u32,
+    // The number of events reaped from the ring, not from the backlog.
+    ring_reaped: u32,
 }
 
 impl<'a> Reaper<'a> {
     fn new(cq: &'a mut Cq, len: u32) -> Self {
-        Self { cq, len, reaped: 0 }
+        Self {
+            cq,
+            len,
+            reaped: 0,
+            ring_reaped: 0,
+        }
     }
 }
 
@@ -116,12 +164,38 @@ impl Iterator for Reaper<'_> {
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.reaped < self.len {
+            self.reaped += 1;
+            if let Some(cqe) = self.cq.backlog.pop_front() {
+                return Some(cqe);
+            }
+
             unsafe {
                 let head = *(self.cq.head as *const u32);
-                let idx = head.wrapping_add(self.reaped) & *self.cq.ring_mask;
-                let cqe = self.cq.cqes.add(idx as _).as_ref().expect("cqe is null");
-                self.reaped += 1;
-                Some(Cqe::new(cqe))
+                let idx = head.wrapping_add(self.ring_reaped) & *self.cq.ring_mask;
+                let (cqe, big) = if self.cq.uring.has_big_cqe() {
+                    // A big CQE takes up two slots, the latter is the extra data.
+                    let cqe = self.cq.cqes.add(2 * idx as usize);
+                    (cqe, Some(*(cqe.add(1) as *const [u64; 2])))
+                } else {
+                    (self.cq.cqes.add(idx as _), None)
+                };
+                let cqe = cqe.as_ref().expect("cqe is null");
+                self.ring_reaped += 1;
+
+                if cqe.user_data & OWNED_USER_DATA != 0 {
+                    // Hand back the `user_data` and the buffer of the tracked
+                    // operation, which is kept until its last event.
+                    let more = cqe.flags & IORING_CQE_F_MORE != 0;
+                    let owned = self.cq.uring.owned().reap(cqe.user_data, cqe.res, more);
+                    if let Some((user_data, buf, group)) = owned {
+                        let cqe = Cqe::new(cqe, big).with_buf(user_data, buf);
+                        return Some(match group {
+                            Some(bgid) => cqe.with_group(self.cq.uring.id(), bgid),
+                            None => cqe,
+                        });
+                    }
+                }
+                Some(Cqe::new(cqe, big))
             }
         } else {
             None
@@ -131,9 +205,12 @@ impl Iterator for Reaper<'_> {
 
 impl Drop for Reaper<'_> {
     fn drop(&mut self) {
+        // The rest of events are discarded, but the tracked operations must be
+        // accounted for.
+        self.for_each(drop);
         unsafe {
             let head = *(self.cq.head as *const u32);
-            (*self.cq.head).store(head.wrapping_add(self.len), Ordering::Release);
+            (*self.cq.head).store(head.wrapping_add(self.ring_reaped), Ordering::Release);
         }
     }
 }
@@ -1,16 +1,19 @@
 pub mod cq;
 pub use cq::{Cq, Reaper};
 
+pub mod scope;
+pub use scope::Scope;
+
 pub mod sq;
 pub use sq::Sq;
 
-mod util;
+pub(crate) mod util;
 
 use std::{io, mem, sync::Arc};
 
 use crate::sys::{
     io_uring_cqe, io_uring_params, io_uring_sqe, IORING_FEAT_SINGLE_MMAP, IORING_OFF_CQ_RING,
-    IORING_OFF_SQES, IORING_OFF_SQ_RING,
+    IORING_OFF_SQES, IORING_OFF_SQ_RING, IORING_SETUP_CQE32, IORING_SETUP_SQE128,
 };
 
 use self::util::Mmap;
@@ -18,7 +21,7 @@ use self::util::Mmap;
 pub(crate) fn mmap(fd: i32, p: &io_uring_params) -> io::Result<(Arc<Mmap>, Arc<Mmap>, Mmap)> {
     // mmap rings
     let sqr_len = p.sq_off.array as usize + p.sq_entries as usize * mem::size_of::<u32>();
-    let cqr_len = p.cq_off.cqes as usize + p.cq_entries as usize * mem::size_of::<io_uring_cqe>();
+    let cqr_len = p.cq_off.cqes as usize + p.cq_entries as usize * cqe_size(p.flags);
 
     let (sqring, cqring) = if p.features & IORING_FEAT_SINGLE_MMAP != 0 {
         let sqr = Arc::new(Mmap::new(fd, sqr_len.max(cqr_len), IORING_OFF_SQ_RING)?);
@@ -31,8 +34,18 @@ pub(crate) fn mmap(fd: i32, p: &io_uring_params) -> io::Result<(Arc<Mmap>, Arc<M
     };
 
     // mmap sqe array
-    let sqes_len = p.sq_entries as usize * mem::size_of::<io_uring_sqe>();
+    let sqes_len = p.sq_entries as usize * sqe_size(p.flags);
     let sqes = Mmap::new(fd, sqes_len, IORING_OFF_SQES)?;
 
     Ok((sqring, cqring, sqes))
 }
+
+/// Returns the size of a SQE for the ring which is set up with `flags`.
+fn sqe_size(flags: u32) -> usize {
+    mem::size_of::<io_uring_sqe>() << (flags & IORING_SETUP_SQE128 != 0) as usize
+}
+
+/// Returns the size of a CQE for the ring which is set up with `flags`.
+fn cqe_size(flags: u32) -> usize {
+    mem::size_of::<io_uring_cqe>() << (flags & IORING_SETUP_CQE32 != 0) as usize
+}
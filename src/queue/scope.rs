@@ -0,0 +1,247 @@
+use std::{io, marker::PhantomData, os::unix::io::RawFd, process, sync::Arc};
+
+use crate::{op::MsgFlags, Cq, Cqe, Packer, Reaper, Sq, Uring};
+
+/// A scope to submit operations which borrow data from the enclosing frame,
+/// which is created by [`Sq::scope`].
+///
+/// The operations packed up by its methods (e.g. [`read`]) are tracked, and
+/// the scope doesn't end until all of them are completed. So the borrowed data
+/// is never freed while the kernel accesses it.
+///
+/// The [`Sq`] and [`Cq`] are not handed out, since the scope relies on them
+/// to wait for the operations. They are used by its methods instead (e.g.
+/// [`submit_and_wait`] and [`reap_cqe`]).
+///
+/// [`read`]: method@Self::read
+/// [`submit_and_wait`]: method@Self::submit_and_wait
+/// [`reap_cqe`]: method@Self::reap_cqe
+pub struct Scope<'a, 'env> {
+    uring: Arc<Uring>,
+    sq: &'a mut Sq,
+    cq: &'a mut Cq,
+    // `user_data` of the tracked operations in flight.
+    tracked: Vec<u64>,
+    // Invariant over 'env, so that it can't be shrunk to the scope.
+    _env: PhantomData<&'env mut &'env ()>,
+}
+
+impl<'a, 'env> Scope<'a, 'env> {
+    pub(crate) fn new(uring: Arc<Uring>, sq: &'a mut Sq, cq: &'a mut Cq) -> Self {
+        Self {
+            uring,
+            sq,
+            cq,
+            tracked: Vec::new(),
+            _env: PhantomData,
+        }
+    }
+
+    /// Submit the operations to the kernel, see [`Sq::submit`].
+    ///
+    /// # Errors
+    ///
+    /// If the submission fails, then an error is returned.
+    ///
+    /// [`Sq::submit`]: method@crate::Sq::submit
+    #[inline]
+    pub fn submit(&mut self) -> io::Result<usize> {
+        self.sq.submit()
+    }
+
+    /// Submit the operations to the kernel and wait for `min_complete` events,
+    /// see [`Sq::submit_and_wait`].
+    ///
+    /// # Errors
+    ///
+    /// If the submission fails, then an error is returned.
+    ///
+    /// [`Sq::submit_and_wait`]: method@crate::Sq::submit_and_wait
+    #[inline]
+    pub fn submit_and_wait(&mut self, min_complete: u32) -> io::Result<usize> {
+        self.sq.submit_and_wait(min_complete)
+    }
+
+    /// Reap a CQE(Completion Queue Event), see [`Cq::reap_cqe`].
+    ///
+    /// # Errors
+    ///
+    /// If the CQ(Completion Queue) is empty, then an error is returned.
+    ///
+    /// [`Cq::reap_cqe`]: method@crate::Cq::reap_cqe
+    #[inline]
+    pub fn reap_cqe(&mut self) -> Result<Cqe, &'static str> {
+        self.cq.reap_cqe()
+    }
+
+    /// Reap the exact `want` CQEs, see [`Cq::reap_cqes`].
+    ///
+    /// # Errors
+    ///
+    /// If CQEs in the CQ(Completion Queue) is less than `want`, then an error
+    /// is returned.
+    ///
+    /// [`Cq::reap_cqes`]: method@crate::Cq::reap_cqes
+    #[inline]
+    pub fn reap_cqes(&mut self, want: usize) -> Result<Reaper<'_>, &'static str> {
+        self.cq.reap_cqes(want)
+    }
+
+    /// Allocate a SQE packed up for the operation that reads from the file
+    /// descriptor `fd` into the `buf`, which is borrowed until the scope ends.
+    /// Returns the [`Packer`] to pack the rest of data such as `user_data`.
+    ///
+    /// It's similar to pread(2). If the file is not seekable, `offset` must
+    /// be set to zero.
+    ///
+    /// **Available since kernel 5.6.**
+    ///
+    /// # Errors
+    ///
+    /// If the SQ is full, then an error is returned.
+    pub fn read(
+        &mut self,
+        fd: RawFd,
+        buf: &'env mut [u8],
+        offset: u64,
+    ) -> Result<Packer<'_>, &'static str> {
+        self.track(|packer| packer.packup_read(fd, buf, offset))
+    }
+
+    /// Allocate a SQE packed up for the operation that writes the `buf`, which
+    /// is borrowed until the scope ends, to the file descriptor `fd`. Returns
+    /// the [`Packer`] to pack the rest of data such as `user_data`.
+    ///
+    /// It's similar to pwrite(2). If the file is not seekable, `offset` must
+    /// be set to zero.
+    ///
+    /// **Available since kernel 5.6.**
+    ///
+    /// # Errors
+    ///
+    /// If the SQ is full, then an error is returned.
+    pub fn write(
+        &mut self,
+        fd: RawFd,
+        buf: &'env [u8],
+        offset: u64,
+    ) -> Result<Packer<'_>, &'static str> {
+        self.track(|packer| packer.packup_write(fd, buf, offset))
+    }
+
+    /// Allocate a SQE packed up for the operation that receives a message
+    /// from the socket `fd` into the `buf`, which is borrowed until the scope
+    /// ends. Returns the [`Packer`] to pack the rest of data such as
+    /// `user_data`.
+    ///
+    /// It's similar to recv(2).
+    ///
+    /// **Available since kernel 5.6.**
+    ///
+    /// # Errors
+    ///
+    /// If the SQ is full, then an error is returned.
+    pub fn recv(
+        &mut self,
+        fd: RawFd,
+        buf: &'env mut [u8],
+        flags: MsgFlags,
+    ) -> Result<Packer<'_>, &'static str> {
+        self.track(|packer| packer.packup_recv(fd, buf, flags))
+    }
+
+    /// Allocate a SQE packed up for the operation that sends the `buf`, which
+    /// is borrowed until the scope ends, on the connected socket `fd`. Returns
+    /// the [`Packer`] to pack the rest of data such as `user_data`.
+    ///
+    /// It's similar to send(2).
+    ///
+    /// **Available since kernel 5.6.**
+    ///
+    /// # Errors
+    ///
+    /// If the SQ is full, then an error is returned.
+    pub fn send(
+        &mut self,
+        fd: RawFd,
+        buf: &'env [u8],
+        flags: MsgFlags,
+    ) -> Result<Packer<'_>, &'static str> {
+        self.track(|packer| packer.packup_send(fd, buf, flags))
+    }
+
+    fn track(&mut self, pack: impl FnOnce(&mut Packer<'_>)) -> Result<Packer<'_>, &'static str> {
+        let mut packer = self.sq.alloc_sqe()?;
+        pack(&mut packer);
+        self.tracked.push(packer.track());
+        Ok(packer)
+    }
+
+    /// Cancel the tracked operations in flight.
+    pub(crate) fn cancel(&mut self) {
+        self.check();
+        let tracked: Vec<_> = {
+            let mut registry = self.uring.owned();
+            self.tracked
+                .iter()
+                .copied()
+                .filter(|&owned| registry.contains(owned))
+                .collect()
+        };
+
+        for owned in tracked {
+            let cancel = loop {
+                match self.sq.alloc_sqe() {
+                    Ok(mut packer) => break packer.packup_cancel_tracked(owned),
+                    // Make room for it.
+                    Err(_) => self.submit_internal(),
+                }
+            };
+            self.tracked.push(cancel);
+        }
+    }
+
+    /// Wait until all the tracked operations are completed. Their events are
+    /// kept in the CQ, and can be reaped after the scope ends.
+    pub(crate) fn wait(&mut self) {
+        self.check();
+        loop {
+            self.cq.stash();
+            {
+                let mut registry = self.uring.owned();
+                self.tracked.retain(|&owned| registry.contains(owned));
+            }
+            if self.tracked.is_empty() {
+                return;
+            }
+
+            // The ring is vacated, so it waits for a new event.
+            if let Err(err) = self.sq.submit_and_wait(1) {
+                Self::handle(err);
+            }
+        }
+    }
+
+    fn submit_internal(&mut self) {
+        if let Err(err) = self.sq.submit() {
+            Self::handle(err);
+        }
+    }
+
+    // The queues must be of the io_uring instance tracking the operations, or
+    // their events are never accounted for.
+    fn check(&self) {
+        if !self.sq.belongs_to(&self.uring) || !self.cq.belongs_to(&self.uring) {
+            process::abort();
+        }
+    }
+
+    fn handle(err: io::Error) {
+        match err.raw_os_error() {
+            Some(libc::EINTR) | Some(libc::EAGAIN) | Some(libc::EBUSY) => {}
+            // The scope can't end while the kernel may access the borrowed
+            // data, and there's no way to wait for it.
+            _ => process::abort(),
+        }
+    }
+}
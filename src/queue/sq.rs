@@ -1,6 +1,7 @@
 use std::{
     io,
     ops::Deref,
+    panic::{self, AssertUnwindSafe},
     ptr,
     sync::{
         atomic::{AtomicU32, Ordering},
@@ -9,6 +10,7 @@ use std::{
 };
 
 use crate::{
+    op::Operation,
     resultify,
     sys::{
         self, io_sqring_offsets, io_uring_sqe, IORING_ENTER_GETEVENTS, IORING_ENTER_SQ_WAKEUP,
@@ -17,7 +19,7 @@ use crate::{
     Packer, Uring,
 };
 
-use super::util::Mmap;
+use super::{util::Mmap, Cq, Scope};
 
 /// Submission Queue.
 pub struct Sq {
@@ -28,9 +30,10 @@ pub struct Sq {
     ring_mask: *const u32,
     ring_entries: *const u32,
     flags: *const AtomicU32,
+    #[allow(dead_code)]
     dropped: *const AtomicU32,
     array: *mut u32,
-    ring: Arc<Mmap>,
+    _ring: Arc<Mmap>,
 
     sqe_head: u32,
     sqe_tail: u32,
@@ -55,7 +58,7 @@ impl Sq {
                 flags: ring.add(offset.flags as _) as _,
                 dropped: ring.add(offset.dropped as _) as _,
                 array: ring.add(offset.array as _) as _,
-                ring,
+                _ring: ring,
 
                 sqe_head: 0,
                 sqe_tail: 0,
@@ -75,25 +78,46 @@ impl Sq {
     /// # Errors
     ///
     /// If the SQ is full, then an error is returned.
-    pub fn alloc_sqe(&mut self) -> Result<Packer, &'static str> {
+    pub fn alloc_sqe(&mut self) -> Result<Packer<'_>, &'static str> {
         unsafe {
             let head = (*self.head).load(Ordering::Acquire);
             let next = self.sqe_tail.wrapping_add(1);
 
             if next.wrapping_sub(head) <= *self.ring_entries {
                 let idx = self.sqe_tail & *self.ring_mask;
-                let sqe = (*self.sqes.deref() as *mut io_uring_sqe)
-                    .add(idx as _)
-                    .as_mut()
-                    .unwrap(); // sqes never be a null pointer
+                let sqes = *self.sqes.deref() as *mut io_uring_sqe;
+                let (sqe, ext) = if self.uring.has_big_sqe() {
+                    // A big SQE takes up two slots, the latter is the extension.
+                    let sqe = sqes.add(2 * idx as usize);
+                    (sqe, (sqe.add(1) as *mut [u8; 64]).as_mut())
+                } else {
+                    (sqes.add(idx as _), None)
+                };
                 self.sqe_tail = next;
-                Ok(Packer::new(sqe))
+                Ok(Packer::new(sqe.as_mut().unwrap(), ext, &self.uring)) // sqes never be a null pointer
             } else {
                 Err("Submission Queue is full")
             }
         }
     }
 
+    /// Allocate a vacant SQE and pack up the `op` into it. Returns the
+    /// [`Packer`] to pack the rest of data such as `user_data`.
+    ///
+    /// # Errors
+    ///
+    /// If the SQ is full, then the `op` is handed back as an error, so it can
+    /// be pushed again after submission.
+    pub fn push<O: Operation>(&mut self, op: O) -> Result<Packer<'_>, O> {
+        match self.alloc_sqe() {
+            Ok(mut packer) => {
+                op.prepare(&mut packer);
+                Ok(packer)
+            }
+            Err(_) => Err(op),
+        }
+    }
+
     /// Flush SQEs to the SQ ring for preparing submission. Returns the number
     /// of pending items in the SQ ring.
     fn flush(&mut self) -> u32 {
@@ -148,6 +172,47 @@ impl Sq {
         Ok(resultify(ret)? as _)
     }
 
+    /// Create a [`Scope`] to submit operations which borrow data from the
+    /// enclosing frame, and run `f` in it.
+    ///
+    /// Like [`std::thread::scope`], the data borrowed by the operations of the
+    /// scope must outlive the scope, and all of them are completed before it
+    /// returns. If `f` panics, they are canceled, and the panic is propagated
+    /// once they are completed. Their events are kept in the `cq` even if the
+    /// scope waits for them, and can be reaped after it ends.
+    ///
+    /// The events of the operations can't be skipped by
+    /// `IOSQE_CQE_SKIP_SUCCESS`, since the scope waits for them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `cq` doesn't belong to the same io_uring instance.
+    ///
+    /// [`std::thread::scope`]: function@std::thread::scope
+    pub fn scope<'env, F, T>(&mut self, cq: &mut Cq, f: F) -> T
+    where
+        F: for<'a> FnOnce(&mut Scope<'a, 'env>) -> T,
+    {
+        assert!(
+            cq.belongs_to(&self.uring),
+            "Cq belongs to another io_uring instance"
+        );
+
+        let mut scope = Scope::new(self.uring.clone(), self, cq);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| f(&mut scope)));
+        if result.is_err() {
+            scope.cancel();
+        }
+        scope.wait();
+        result.unwrap_or_else(|payload| panic::resume_unwind(payload))
+    }
+
+    /// Returns `true` if the `uring` is the one which the SQ belongs to.
+    #[inline]
+    pub(crate) fn belongs_to(&self, uring: &Arc<Uring>) -> bool {
+        Arc::ptr_eq(&self.uring, uring)
+    }
+
     /// Returns the number of entries the SQ can hold.
     #[inline]
     pub fn capacity(&self) -> usize {
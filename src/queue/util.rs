@@ -1,4 +1,7 @@
-use libc::{c_void, off_t, size_t, MAP_FAILED, MAP_POPULATE, MAP_SHARED, PROT_READ, PROT_WRITE};
+use libc::{
+    c_void, off_t, size_t, MAP_ANONYMOUS, MAP_FAILED, MAP_POPULATE, MAP_PRIVATE, MAP_SHARED,
+    PROT_READ, PROT_WRITE,
+};
 use std::{io, ops::Deref, ptr};
 
 /// A memory mapped io_uring component.
@@ -24,6 +27,23 @@ impl Mmap {
             ptr => Ok(Mmap { ptr, len }),
         }
     }
+
+    /// Creates a zero-filled anonymous memory map, which is page-aligned.
+    pub fn anonymous(len: usize) -> io::Result<Mmap> {
+        match unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                len as size_t,
+                PROT_READ | PROT_WRITE,
+                MAP_PRIVATE | MAP_ANONYMOUS | MAP_POPULATE,
+                -1,
+                0,
+            )
+        } {
+            MAP_FAILED => Err(io::Error::last_os_error()),
+            ptr => Ok(Mmap { ptr, len }),
+        }
+    }
 }
 
 impl Drop for Mmap {
@@ -35,6 +55,11 @@ impl Drop for Mmap {
     }
 }
 
+// The mapping is owned exclusively and only unmapped on drop, so the address
+// can be sent and shared between threads.
+unsafe impl Send for Mmap {}
+unsafe impl Sync for Mmap {}
+
 impl Deref for Mmap {
     type Target = *mut c_void;
 
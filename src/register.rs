@@ -1,12 +1,19 @@
 use std::{
     io::{self, IoSlice},
+    mem,
+    os::unix::io::RawFd,
     ptr,
     sync::Arc,
 };
 
 use crate::{
+    buf_ring::{BufRing, BufRingFlags},
     resultify,
-    sys::{self, IORING_REGISTER_BUFFERS, IORING_UNREGISTER_BUFFERS},
+    sys::{
+        self, io_uring_rsrc_register, IORING_REGISTER_BUFFERS, IORING_REGISTER_FILES,
+        IORING_REGISTER_FILES2, IORING_RSRC_REGISTER_SPARSE, IORING_UNREGISTER_BUFFERS,
+        IORING_UNREGISTER_FILES,
+    },
     Uring,
 };
 
@@ -84,6 +91,113 @@ impl Registrar {
         resultify(ret)?;
         Ok(())
     }
+
+    /// Register a slice of file descriptors as the fixed file table.
+    ///
+    /// The registered files can be used by SQEs flagged with
+    /// `IOSQE_FIXED_FILE`, where the `fd` is the index into the table instead
+    /// of the file descriptor. A slot filled with `-1` is left empty and can be
+    /// filled later, for example by the operations which install direct
+    /// descriptors (e.g. [`packup_accept_multi_direct`]).
+    ///
+    /// Only one table can be registered at a time. It's released when
+    /// [`unregister_files`] is called or the io_uring instance is torn down.
+    ///
+    /// **Available since kernel 5.1.**
+    ///
+    /// [`packup_accept_multi_direct`]:method@crate::Packer::packup_accept_multi_direct
+    /// [`unregister_files`]:method@Self::unregister_files
+    pub fn register_files(&self, fds: &[RawFd]) -> io::Result<()> {
+        let ret = unsafe {
+            sys::io_uring_register(
+                self.uring.fd,
+                IORING_REGISTER_FILES,
+                fds.as_ptr() as _,
+                fds.len() as _,
+            )
+        };
+        resultify(ret)?;
+        Ok(())
+    }
+
+    /// Register the fixed file table of the `nr` empty slots.
+    ///
+    /// It's like [`register_files`] with all `-1` file descriptors, but the
+    /// kernel doesn't need to copy and check the array.
+    ///
+    /// **Available since kernel 5.19.**
+    ///
+    /// [`register_files`]:method@Self::register_files
+    pub fn register_files_sparse(&self, nr: u32) -> io::Result<()> {
+        let reg = io_uring_rsrc_register {
+            nr,
+            flags: IORING_RSRC_REGISTER_SPARSE,
+            ..Default::default()
+        };
+        let ret = unsafe {
+            sys::io_uring_register(
+                self.uring.fd,
+                IORING_REGISTER_FILES2,
+                &reg as *const _ as _,
+                mem::size_of::<io_uring_rsrc_register>() as _,
+            )
+        };
+        resultify(ret)?;
+        Ok(())
+    }
+
+    /// Register a [`BufRing`] identified by the `bgid`, which consists of
+    /// `entries` buffers of `buf_len` bytes. `entries` must be a power of 2,
+    /// up to 32768.
+    ///
+    /// All the buffers are handed to the kernel at once, and the ring is
+    /// unregistered when the returned [`BufRing`] is dropped.
+    ///
+    /// **Available since kernel 5.19.**
+    pub fn register_buf_ring(
+        &self,
+        bgid: u16,
+        entries: u16,
+        buf_len: usize,
+        flags: BufRingFlags,
+    ) -> io::Result<BufRing> {
+        let mut buf_ring = self.register_buf_ring_empty(bgid, entries, buf_len, flags)?;
+        for bid in 0..entries {
+            buf_ring.push(bid);
+        }
+        buf_ring.commit();
+        Ok(buf_ring)
+    }
+
+    /// Like [`register_buf_ring`], but none of the buffers is in the ring
+    /// initially.
+    ///
+    /// It's used to queue the data to be sent by [`packup_send_bundle`].
+    ///
+    /// **Available since kernel 5.19.**
+    ///
+    /// [`register_buf_ring`]: method@Self::register_buf_ring
+    /// [`packup_send_bundle`]: method@crate::Packer::packup_send_bundle
+    pub fn register_buf_ring_empty(
+        &self,
+        bgid: u16,
+        entries: u16,
+        buf_len: usize,
+        flags: BufRingFlags,
+    ) -> io::Result<BufRing> {
+        BufRing::new(Arc::clone(&self.uring), bgid, entries, buf_len, flags)
+    }
+
+    /// Unregister the fixed file table.
+    ///
+    /// **Available since kernel 5.1.**
+    pub fn unregister_files(&self) -> io::Result<()> {
+        let ret = unsafe {
+            sys::io_uring_register(self.uring.fd, IORING_UNREGISTER_FILES, ptr::null(), 0)
+        };
+        resultify(ret)?;
+        Ok(())
+    }
 }
 
 unsafe impl Send for Registrar {}
@@ -1,6 +1,6 @@
 #![allow(deref_nullptr)]
 #![allow(nonstandard_style)]
-#![allow(unaligned_references)]
+#![allow(dead_code)]
 
 pub mod syscall;
 pub use syscall::*;
@@ -31,6 +31,11 @@ pub const IORING_SETUP_CQSIZE: __u32 = 1 << 3; // app defines CQ size
 pub const IORING_SETUP_CLAMP: __u32 = 1 << 4; // clamp SQ/CQ ring sizes
 pub const IORING_SETUP_ATTACH_WQ: __u32 = 1 << 5; // attach to existing wq
 pub const IORING_SETUP_R_DISABLED: __u32 = 1 << 6; // start with ring disabled
+pub const IORING_SETUP_SUBMIT_ALL: __u32 = 1 << 7; // continue submit on error
+pub const IORING_SETUP_COOP_TASKRUN: __u32 = 1 << 8; // cooperative task running
+pub const IORING_SETUP_TASKRUN_FLAG: __u32 = 1 << 9; // set IORING_SQ_TASKRUN
+pub const IORING_SETUP_SQE128: __u32 = 1 << 10; // SQEs are 128 byte
+pub const IORING_SETUP_CQE32: __u32 = 1 << 11; // CQEs are 32 byte
 
 // io_uring_params.features
 pub const IORING_FEAT_SINGLE_MMAP: __u32 = 1 << 0;
@@ -125,6 +130,13 @@ impl Default for io_uring_sqe {
 pub union io_uring_sqe__bindgen_ty_1 {
     pub off: __u64, //offset into file
     pub addr2: __u64,
+    pub __bindgen_anon_1: io_uring_sqe__bindgen_ty_1__bindgen_ty_1,
+}
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct io_uring_sqe__bindgen_ty_1__bindgen_ty_1 {
+    pub cmd_op: __u32,
+    pub __pad1: __u32,
 }
 impl Default for io_uring_sqe__bindgen_ty_1 {
     fn default() -> Self {
@@ -140,6 +152,13 @@ impl Default for io_uring_sqe__bindgen_ty_1 {
 pub union io_uring_sqe__bindgen_ty_2 {
     pub addr: __u64, // pointer to buffer or iovecs
     pub splice_off_in: __u64,
+    pub __bindgen_anon_1: io_uring_sqe__bindgen_ty_2__bindgen_ty_1,
+}
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct io_uring_sqe__bindgen_ty_2__bindgen_ty_1 {
+    pub level: __u32,
+    pub optname: __u32,
 }
 impl Default for io_uring_sqe__bindgen_ty_2 {
     fn default() -> Self {
@@ -170,6 +189,12 @@ pub union io_uring_sqe__bindgen_ty_3 {
     pub rename_flags: __u32,
     pub unlink_flags: __u32,
     pub hardlink_flags: __u32,
+    pub msg_ring_flags: __u32,
+    pub xattr_flags: __u32,
+    pub futex_flags: __u32,
+    pub waitid_flags: __u32,
+    pub install_fd_flags: __u32,
+    pub uring_cmd_flags: __u32,
 }
 impl Default for io_uring_sqe__bindgen_ty_3 {
     fn default() -> Self {
@@ -202,6 +227,7 @@ impl Default for io_uring_sqe__bindgen_ty_4 {
 pub union io_uring_sqe__bindgen_ty_5 {
     pub splice_fd_in: __s32,
     pub file_index: __u32,
+    pub optlen: __u32,
 }
 impl Default for io_uring_sqe__bindgen_ty_5 {
     fn default() -> Self {
@@ -220,6 +246,7 @@ pub const IOSQE_IO_LINK: __u8 = 1 << 2; // links next sqe
 pub const IOSQE_IO_HARDLINK: __u8 = 1 << 3; // like LINK, but stronger
 pub const IOSQE_ASYNC: __u8 = 1 << 4; // always go async
 pub const IOSQE_BUFFER_SELECT: __u8 = 1 << 5; // select buffer from sqe->buf_group
+pub const IOSQE_CQE_SKIP_SUCCESS: __u8 = 1 << 6; // don't post CQE if request succeeded
 
 // opcode
 pub const IORING_OP_NOP: __u8 = 0;
@@ -262,7 +289,25 @@ pub const IORING_OP_UNLINKAT: __u8 = 36;
 pub const IORING_OP_MKDIRAT: __u8 = 37;
 pub const IORING_OP_SYMLINKAT: __u8 = 38;
 pub const IORING_OP_LINKAT: __u8 = 39;
-pub const IORING_OP_LAST: __u8 = 40; // this goes last, obviously
+pub const IORING_OP_MSG_RING: __u8 = 40;
+pub const IORING_OP_FSETXATTR: __u8 = 41;
+pub const IORING_OP_SETXATTR: __u8 = 42;
+pub const IORING_OP_FGETXATTR: __u8 = 43;
+pub const IORING_OP_GETXATTR: __u8 = 44;
+pub const IORING_OP_SOCKET: __u8 = 45;
+pub const IORING_OP_URING_CMD: __u8 = 46;
+pub const IORING_OP_SEND_ZC: __u8 = 47;
+pub const IORING_OP_SENDMSG_ZC: __u8 = 48;
+pub const IORING_OP_WAITID: __u8 = 50;
+pub const IORING_OP_FUTEX_WAIT: __u8 = 51;
+pub const IORING_OP_FUTEX_WAKE: __u8 = 52;
+pub const IORING_OP_FUTEX_WAITV: __u8 = 53;
+pub const IORING_OP_FIXED_FD_INSTALL: __u8 = 54;
+pub const IORING_OP_FTRUNCATE: __u8 = 55;
+pub const IORING_OP_BIND: __u8 = 56;
+pub const IORING_OP_LISTEN: __u8 = 57;
+pub const IORING_OP_EPOLL_WAIT: __u8 = 59;
+pub const IORING_OP_LAST: __u8 = 60; // this goes last, obviously
 
 // sqe.fsync_flags
 pub const IORING_FSYNC_DATASYNC: __u32 = 1 << 0;
@@ -274,9 +319,17 @@ pub const IORING_TIMEOUT_BOOTTIME: u32 = 1 << 2;
 pub const IORING_TIMEOUT_REALTIME: u32 = 1 << 3;
 pub const IORING_LINK_TIMEOUT_UPDATE: u32 = 1 << 4;
 pub const IORING_TIMEOUT_ETIME_SUCCESS: u32 = 1 << 5;
+pub const IORING_TIMEOUT_MULTISHOT: u32 = 1 << 6;
 pub const IORING_TIMEOUT_CLOCK_MASK: u32 = IORING_TIMEOUT_BOOTTIME | IORING_TIMEOUT_REALTIME;
 pub const IORING_TIMEOUT_UPDATE_MASK: u32 = IORING_TIMEOUT_UPDATE | IORING_LINK_TIMEOUT_UPDATE;
 
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct __kernel_timespec {
+    pub tv_sec: i64,  // __kernel_time64_t
+    pub tv_nsec: i64, // long long
+}
+
 // sqe.splice_flags
 // extends splice(2) flags
 pub const SPLICE_F_FD_IN_FIXED: __u32 = 1 << 31; // the last bit of __u32
@@ -294,6 +347,61 @@ pub const IORING_POLL_ADD_MULTI: __u32 = 1 << 0;
 pub const IORING_POLL_UPDATE_EVENTS: __u32 = 1 << 1;
 pub const IORING_POLL_UPDATE_USER_DATA: __u32 = 1 << 2;
 
+// send/sendmsg and recv/recvmsg flags (sqe->ioprio)
+//
+// IORING_RECV_MULTISHOT	Multishot recv. Sets IORING_CQE_F_MORE if
+//				the handler will continue to report
+//				CQEs on behalf of the same SQE.
+//
+// IORING_RECVSEND_FIXED_BUF	Use registered buffers, the index is stored in
+//				the buf_index field.
+//
+// IORING_RECVSEND_BUNDLE	Used with IOSQE_BUFFER_SELECT. If set, send or
+//				recv will grab as many buffers from the buffer
+//				group ID given and send them all. The completion
+//				result will be the number of buffers send, with
+//				the starting buffer ID in cqe->flags as per
+//				usual for provided buffer usage. The buffers
+//				will be contiguous from the starting buffer ID.
+pub const IORING_RECV_MULTISHOT: __u16 = 1 << 1;
+pub const IORING_RECVSEND_FIXED_BUF: __u16 = 1 << 2;
+pub const IORING_RECVSEND_BUNDLE: __u16 = 1 << 4;
+
+// accept flags stored in sqe->ioprio
+pub const IORING_ACCEPT_MULTISHOT: __u16 = 1 << 0;
+
+// IORING_OP_MSG_RING command types, stored in sqe->addr
+pub const IORING_MSG_DATA: __u64 = 0; // pass sqe->len as 'res' and off as user_data
+pub const IORING_MSG_SEND_FD: __u64 = 1; // send a registered fd to another ring
+
+// IORING_OP_MSG_RING flags (sqe->msg_ring_flags)
+//
+// IORING_MSG_RING_CQE_SKIP	Don't post a CQE to the target ring. Not
+//				applicable for IORING_MSG_DATA, obviously.
+pub const IORING_MSG_RING_CQE_SKIP: __u32 = 1 << 0;
+// Pass through the flags from sqe->file_index to cqe->flags
+pub const IORING_MSG_RING_FLAGS_PASS: __u32 = 1 << 1;
+
+// sqe->uring_cmd_flags		top 8bits aren't available for userspace
+//
+// IORING_URING_CMD_FIXED	use registered buffer; pass this flag
+//				along with setting sqe->buf_index.
+pub const IORING_URING_CMD_FIXED: __u32 = 1 << 0;
+
+// Argument for IORING_OP_URING_CMD when file is a socket
+pub const SOCKET_URING_OP_SIOCINQ: __u32 = 0;
+pub const SOCKET_URING_OP_SIOCOUTQ: __u32 = 1;
+pub const SOCKET_URING_OP_GETSOCKOPT: __u32 = 2;
+pub const SOCKET_URING_OP_SETSOCKOPT: __u32 = 3;
+
+// IORING_OP_FIXED_FD_INSTALL flags (sqe->install_fd_flags)
+//
+// IORING_FIXED_FD_NO_CLOEXEC	Don't mark the fd as O_CLOEXEC
+pub const IORING_FIXED_FD_NO_CLOEXEC: __u32 = 1 << 0;
+
+// sqe->file_index value which allocates a free slot of the fixed file table
+pub const IORING_FILE_INDEX_ALLOC: __u32 = !0;
+
 // IO completion data structure (Completion Queue Entry)
 #[repr(C)]
 #[derive(Debug, Default, Copy, Clone)]
@@ -306,6 +414,9 @@ pub struct io_uring_cqe {
 // cqe.flags
 pub const IORING_CQE_F_BUFFER: __u32 = 1 << 0; // the upper 16 bits are the buffer ID
 pub const IORING_CQE_F_MORE: __u32 = 1 << 1; // parent SQE will generate more CQE entries
+pub const IORING_CQE_F_SOCK_NONEMPTY: __u32 = 1 << 2; // more data to read after socket recv
+pub const IORING_CQE_F_NOTIF: __u32 = 1 << 3; // notification CQE, to distinguish them from sends
+pub const IORING_CQE_F_BUF_MORE: __u32 = 1 << 4; // the buffer will be used by more completions
 pub const IORING_CQE_BUFFER_SHIFT: ::std::os::raw::c_uint = 16;
 
 /// io_uring_register(2) opcodes and arguments
@@ -332,5 +443,72 @@ pub const IORING_REGISTER_IOWQ_AFF: ::std::os::raw::c_uint = 17;
 pub const IORING_UNREGISTER_IOWQ_AFF: ::std::os::raw::c_uint = 18;
 // set/get max number of async workers
 pub const IORING_REGISTER_IOWQ_MAX_WORKERS: ::std::os::raw::c_uint = 19;
+// register ring based provide buffer group
+pub const IORING_REGISTER_PBUF_RING: ::std::os::raw::c_uint = 22;
+pub const IORING_UNREGISTER_PBUF_RING: ::std::os::raw::c_uint = 23;
 // this goes last
-pub const IORING_REGISTER_LAST: ::std::os::raw::c_uint = 20;
+pub const IORING_REGISTER_LAST: ::std::os::raw::c_uint = 24;
+
+// Register a fully sparse file space, rather than pass in an array of all -1
+// file descriptors.
+pub const IORING_RSRC_REGISTER_SPARSE: __u32 = 1 << 0;
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct io_uring_rsrc_register {
+    pub nr: __u32,
+    pub flags: __u32,
+    pub resv2: __u64,
+    pub data: __u64,
+    pub tags: __u64,
+}
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct io_uring_buf {
+    pub addr: __u64,
+    pub len: __u32,
+    pub bid: __u16,
+    pub resv: __u16,
+}
+
+// The tail of io_uring_buf_ring overlays the `resv` of the first io_uring_buf.
+pub const IO_URING_BUF_RING_TAIL_OFFSET: usize = 14;
+
+// Flags for IORING_REGISTER_PBUF_RING.
+//
+// IOU_PBUF_RING_MMAP:	If set, kernel will allocate the memory for the ring.
+//			The application must not set a ring_addr in struct
+//			io_uring_buf_reg, instead it must subsequently call
+//			mmap(2) with the offset set as:
+//			IORING_OFF_PBUF_RING | (bgid << IORING_OFF_PBUF_SHIFT)
+//			to get a virtual mapping for the ring.
+// IOU_PBUF_RING_INC:	If set, buffers consumed from this buffer ring can be
+//			consumed incrementally. Normally one (or more) buffers
+//			are fully consumed. With incremental consumptions, it's
+//			feasible to register big ranges of buffers, and each
+//			use of it will consume only as much as it needs. This
+//			requires that both the kernel and application keep
+//			track of where the current read/recv index is at.
+pub const IOU_PBUF_RING_MMAP: __u16 = 1;
+pub const IOU_PBUF_RING_INC: __u16 = 2;
+
+// argument for IORING_(UN)REGISTER_PBUF_RING
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct io_uring_buf_reg {
+    pub ring_addr: __u64,
+    pub ring_entries: __u32,
+    pub bgid: __u16,
+    pub flags: __u16,
+    pub resv: [__u64; 3usize],
+}
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct io_uring_recvmsg_out {
+    pub namelen: __u32,
+    pub controllen: __u32,
+    pub payloadlen: __u32,
+    pub flags: __u32,
+}
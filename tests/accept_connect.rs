@@ -0,0 +1,173 @@
+use std::{
+    error::Error,
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+    os::unix::{
+        io::{AsRawFd, FromRawFd},
+        net::{UnixListener, UnixStream},
+    },
+};
+
+use urio::op::{SockAddrStorage, SockFlags};
+
+#[test]
+fn accept_tcp() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let mut client = TcpStream::connect(listener.local_addr()?)?;
+
+    let addr = Box::new(SockAddrStorage::new());
+    sq.alloc_sqe()?
+        .packup_accept(listener.as_raw_fd(), Some(addr), SockFlags::CLOEXEC);
+
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+
+    let mut cqe = cq.reap_cqe()?;
+    let fd = cqe.result()?;
+    let mut server = unsafe { TcpStream::from_raw_fd(fd as _) };
+    let addr = cqe.take_buf::<Box<SockAddrStorage>>().unwrap();
+    assert_eq!(addr.as_socket_addr(), Some(client.local_addr()?));
+
+    client.write_all(b"ping")?;
+    let mut buf = [0; 4];
+    server.read_exact(&mut buf)?;
+    assert_eq!(&buf, b"ping");
+
+    Ok(())
+}
+
+#[test]
+fn accept_unix() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let dir = tempfile::tempdir()?;
+    let listener = UnixListener::bind(dir.path().join("sock"))?;
+    let _client = UnixStream::connect(dir.path().join("sock"))?;
+
+    sq.alloc_sqe()?
+        .packup_accept(listener.as_raw_fd(), None, SockFlags::empty());
+
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+
+    let fd = cq.reap_cqe()?.result()?;
+    let server = unsafe { UnixStream::from_raw_fd(fd as _) };
+    assert!(server.peer_addr()?.is_unnamed());
+
+    Ok(())
+}
+
+#[test]
+fn accept_multi_tcp() -> Result<(), Box<dyn Error>> {
+    const NUM_CONNS: usize = 32;
+    let (mut sq, mut cq, _) = urio::new(NUM_CONNS as _)?;
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let clients = (0..NUM_CONNS)
+        .map(|_| TcpStream::connect(listener.local_addr()?))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    sq.alloc_sqe()?
+        .packup_accept_multi(listener.as_raw_fd(), SockFlags::CLOEXEC);
+
+    let submitted = sq.submit_and_wait(NUM_CONNS as _)?;
+    assert_eq!(submitted, 1);
+
+    let mut peers = Vec::new();
+    for cqe in cq.reap_cqes(NUM_CONNS)? {
+        assert!(cqe.has_more());
+        let server = unsafe { TcpStream::from_raw_fd(cqe.result()? as _) };
+        peers.push(server.peer_addr()?);
+    }
+    for client in clients {
+        assert!(peers.contains(&client.local_addr()?));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn accept_multi_direct_tcp() -> Result<(), Box<dyn Error>> {
+    const NUM_CONNS: usize = 32;
+    let (mut sq, mut cq, rgstr) = urio::new(NUM_CONNS as _)?;
+    rgstr.register_files_sparse(NUM_CONNS as _)?;
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let _clients = (0..NUM_CONNS)
+        .map(|_| TcpStream::connect(listener.local_addr()?))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    sq.alloc_sqe()?
+        // CLOEXEC is stripped, otherwise the kernel rejects it.
+        .packup_accept_multi_direct(listener.as_raw_fd(), SockFlags::CLOEXEC);
+
+    let submitted = sq.submit_and_wait(NUM_CONNS as _)?;
+    assert_eq!(submitted, 1);
+
+    let mut slots = cq
+        .reap_cqes(NUM_CONNS)?
+        .map(|cqe| {
+            assert!(cqe.has_more());
+            cqe.result()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    slots.sort_unstable();
+    assert_eq!(slots, (0..NUM_CONNS as u32).collect::<Vec<_>>());
+
+    Ok(())
+}
+
+#[test]
+fn connect_tcp() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let mut client = socket(libc::AF_INET)?;
+
+    let addr = SockAddrStorage::from(listener.local_addr()?);
+    sq.alloc_sqe()?.packup_connect(client.as_raw_fd(), addr);
+
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+
+    cq.reap_cqe()?.result()?;
+    let (mut server, peer) = listener.accept()?;
+    assert_eq!(peer, client.local_addr()?);
+
+    client.write_all(b"pong")?;
+    let mut buf = [0; 4];
+    server.read_exact(&mut buf)?;
+    assert_eq!(&buf, b"pong");
+
+    Ok(())
+}
+
+#[test]
+fn connect_unix() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("sock");
+    let listener = UnixListener::bind(&path)?;
+    let client = unsafe { UnixStream::from_raw_fd(raw_socket(libc::AF_UNIX)?) };
+
+    let addr = SockAddrStorage::from_unix_path(&path)?;
+    assert_eq!(addr.as_unix_path(), Some(path.as_path()));
+    sq.alloc_sqe()?.packup_connect(client.as_raw_fd(), addr);
+
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+
+    cq.reap_cqe()?.result()?;
+    listener.accept()?;
+    assert_eq!(client.peer_addr()?.as_pathname(), Some(path.as_path()));
+
+    Ok(())
+}
+
+fn socket(domain: i32) -> std::io::Result<TcpStream> {
+    Ok(unsafe { TcpStream::from_raw_fd(raw_socket(domain)?) })
+}
+
+fn raw_socket(domain: i32) -> std::io::Result<i32> {
+    match unsafe { libc::socket(domain, libc::SOCK_STREAM | libc::SOCK_CLOEXEC, 0) } {
+        -1 => Err(std::io::Error::last_os_error()),
+        fd => Ok(fd),
+    }
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use std::{error::Error, io::Write, os::unix::io::AsRawFd};
+
+use urio::Builder;
+
+use common::tcp_pair;
+
+#[test]
+fn big_sqe_cqe_nop() -> Result<(), Box<dyn Error>> {
+    const NUM_ENTRIES: u32 = 4;
+    let (mut sq, mut cq, _) = Builder::new(NUM_ENTRIES).big_sqe().big_cqe().build()?;
+    assert!(sq.uring().has_big_sqe());
+    assert!(cq.uring().has_big_cqe());
+
+    // Go around the rings several times, so that every entry is used.
+    for round in 0..3 {
+        for i in 0..NUM_ENTRIES {
+            sq.alloc_sqe()?
+                .user_data((round * NUM_ENTRIES + i) as _)
+                .packup_nop();
+        }
+        let submitted = sq.submit_and_wait(NUM_ENTRIES)?;
+        assert_eq!(submitted, NUM_ENTRIES as usize);
+
+        for (i, cqe) in cq.reap_cqes(NUM_ENTRIES as _)?.enumerate() {
+            assert_eq!(cqe.user_data(), (round * NUM_ENTRIES) as u64 + i as u64);
+            cqe.result()?;
+            assert_eq!(cqe.big_cqe(), Some(&[0, 0]));
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn normal_cqe() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    assert!(!sq.uring().has_big_sqe());
+    assert!(!cq.uring().has_big_cqe());
+
+    sq.alloc_sqe()?.packup_nop();
+    sq.submit_and_wait(1)?;
+    assert_eq!(cq.reap_cqe()?.big_cqe(), None);
+
+    Ok(())
+}
+
+#[test]
+fn big_sqe_uring_cmd() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = Builder::new(8).big_sqe().build()?;
+    let (mut client, server) = tcp_pair()?;
+
+    client.write_all(b"Hello")?;
+    // Wait until the data arrives.
+    server.peek(&mut [0; 5])?;
+
+    // The socket commands don't care about the command area, but it can be
+    // filled up to the extension.
+    sq.alloc_sqe()?
+        .packup_uring_cmd(server.as_raw_fd(), 0, &[0xff; 80]);
+    sq.submit_and_wait(1)?;
+    assert_eq!(cq.reap_cqe()?.result()?, 5);
+
+    // The `optval` in the command area is not disturbed by the extension.
+    sq.alloc_sqe()?.packup_getsockopt(
+        server.as_raw_fd(),
+        libc::SOL_SOCKET,
+        libc::SO_TYPE,
+        Vec::with_capacity(4),
+    );
+    sq.submit_and_wait(1)?;
+    let mut cqe = cq.reap_cqe()?;
+    assert_eq!(cqe.result()?, 4);
+    let optval = cqe.take_buf::<Vec<u8>>().unwrap();
+    assert_eq!(optval, libc::SOCK_STREAM.to_ne_bytes());
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "command is too long")]
+fn uring_cmd_too_long() {
+    let (mut sq, _, _) = urio::new(8).unwrap();
+    sq.alloc_sqe().unwrap().packup_uring_cmd(0, 0, &[0; 17]);
+}
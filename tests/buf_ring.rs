@@ -0,0 +1,261 @@
+use std::{
+    error::Error,
+    io::Write,
+    os::unix::{io::AsRawFd, net::UnixStream},
+    sync::mpsc,
+    thread,
+};
+
+use urio::{op::MsgFlags, BufRingFlags};
+
+const BGID: u16 = 3;
+
+#[test]
+fn recv_select_buf_ring() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, rgstr) = urio::new(8)?;
+    let (mut tx, rx) = UnixStream::pair()?;
+    let mut buf_ring = rgstr.register_buf_ring(BGID, 2, 16, BufRingFlags::empty())?;
+
+    let mut bids = Vec::new();
+    for msg in [&b"ping"[..], b"pong"] {
+        tx.write_all(msg)?;
+        sq.alloc_sqe()?
+            .packup_recv_select(rx.as_raw_fd(), &buf_ring, MsgFlags::empty());
+        let submitted = sq.submit_and_wait(1)?;
+        assert_eq!(submitted, 1);
+        let cqe = cq.reap_cqe()?;
+        bids.push(cqe.buffer_id().unwrap());
+        assert_eq!(buf_ring.get(&cqe), Some(msg));
+    }
+    assert_ne!(bids[0], bids[1]);
+
+    // All the buffers in the ring are in use.
+    tx.write_all(b"ping")?;
+    sq.alloc_sqe()?
+        .packup_recv_select(rx.as_raw_fd(), &buf_ring, MsgFlags::empty());
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    let err = cq.reap_cqe()?.result().unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ENOBUFS));
+
+    for bid in bids {
+        buf_ring.push(bid);
+    }
+    buf_ring.commit();
+    sq.alloc_sqe()?
+        .packup_recv_select(rx.as_raw_fd(), &buf_ring, MsgFlags::empty());
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    let cqe = cq.reap_cqe()?;
+    let bid = cqe.buffer_id().unwrap();
+    assert_eq!(buf_ring.get(&cqe), Some(&b"ping"[..]));
+    buf_ring.recycle(bid);
+
+    Ok(())
+}
+
+#[test]
+fn read_select_buf_ring_incremental() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, rgstr) = urio::new(8)?;
+    let (mut tx, rx) = UnixStream::pair()?;
+    let mut buf_ring = rgstr.register_buf_ring(BGID, 1, 64, BufRingFlags::INC)?;
+
+    let mut bids = Vec::new();
+    let mut ptrs = Vec::new();
+    for msg in [&b"Lorem ipsum"[..], b" dolor sit amet"] {
+        tx.write_all(msg)?;
+        sq.alloc_sqe()?
+            .packup_read_select(rx.as_raw_fd(), &buf_ring, 0);
+        let submitted = sq.submit_and_wait(1)?;
+        assert_eq!(submitted, 1);
+        let cqe = cq.reap_cqe()?;
+        assert!(cqe.buffer_more());
+        bids.push(cqe.buffer_id().unwrap());
+        let data = buf_ring.get(&cqe).unwrap();
+        assert_eq!(data, msg);
+        ptrs.push(data.as_ptr() as usize);
+    }
+    // Both messages are in the same buffer, which is still in the ring.
+    assert_eq!(bids[0], bids[1]);
+    assert_eq!(ptrs[1], ptrs[0] + 11);
+    assert_eq!(buf_ring.buffer(bids[0], 0, 26), None);
+
+    Ok(())
+}
+
+#[test]
+fn get_from_another_ring() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, rgstr) = urio::new(8)?;
+    let (_, _, other_rgstr) = urio::new(8)?;
+    let (mut tx, rx) = UnixStream::pair()?;
+    let mut buf_ring = rgstr.register_buf_ring(BGID, 1, 16, BufRingFlags::empty())?;
+    // The same group id on another io_uring instance.
+    let mut other = other_rgstr.register_buf_ring(BGID, 1, 16, BufRingFlags::empty())?;
+
+    tx.write_all(b"ping")?;
+    sq.alloc_sqe()?
+        .packup_recv_select(rx.as_raw_fd(), &buf_ring, MsgFlags::empty());
+    sq.submit_and_wait(1)?;
+    let cqe = cq.reap_cqe()?;
+    assert_eq!(cqe.buffer_id(), Some(0));
+    assert_eq!(other.get(&cqe), None);
+    assert!(other.buffer(0, 0, 4).is_none());
+    assert_eq!(buf_ring.get(&cqe), Some(&b"ping"[..]));
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "consumes buffers incrementally")]
+fn bundle_incremental() {
+    let (mut sq, mut cq, rgstr) = urio::new(8).unwrap();
+    let mut buf_ring = rgstr
+        .register_buf_ring(BGID, 1, 16, BufRingFlags::INC)
+        .unwrap();
+
+    sq.alloc_sqe().unwrap().packup_nop();
+    sq.submit_and_wait(1).unwrap();
+    buf_ring.bundle(&cq.reap_cqe().unwrap());
+}
+
+#[test]
+fn recycle_from_threads() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, rgstr) = urio::new(8)?;
+    let (mut tx, rx) = UnixStream::pair()?;
+    let mut buf_ring = rgstr.register_buf_ring(BGID, 4, 16, BufRingFlags::empty())?;
+
+    // The workers return the buffers to the ring concurrently.
+    let workers: Vec<_> = (0..4)
+        .map(|_| {
+            let (sender, receiver) = mpsc::channel::<urio::RingBuf>();
+            let producer = buf_ring.producer();
+            let handle = thread::spawn(move || {
+                let mut received = Vec::new();
+                for buf in receiver {
+                    received.push(buf.to_vec());
+                    producer.push(buf);
+                }
+                received
+            });
+            (sender, handle)
+        })
+        .collect();
+
+    let mut sent = 0;
+    while sent < 64 {
+        let msg = format!("{:04}", sent);
+        tx.write_all(msg.as_bytes())?;
+        loop {
+            sq.alloc_sqe()?
+                .packup_recv_select(rx.as_raw_fd(), &buf_ring, MsgFlags::empty());
+            sq.submit_and_wait(1)?;
+            let cqe = cq.reap_cqe()?;
+            match cqe.result() {
+                // Wait for the workers to return the buffers.
+                Err(err) if err.raw_os_error() == Some(libc::ENOBUFS) => thread::yield_now(),
+                res => {
+                    assert_eq!(res? as usize, msg.len());
+                    let buf = buf_ring.take(&cqe).unwrap();
+                    assert_eq!(&*buf, msg.as_bytes());
+                    workers[sent % 4].0.send(buf)?;
+                    break;
+                }
+            }
+        }
+        sent += 1;
+    }
+
+    let mut received = Vec::new();
+    for (sender, handle) in workers {
+        drop(sender);
+        received.extend(handle.join().unwrap());
+    }
+    received.sort();
+    let expected: Vec<_> = (0..64).map(|i| format!("{:04}", i).into_bytes()).collect();
+    assert_eq!(received, expected);
+
+    Ok(())
+}
+
+#[test]
+fn ring_buf_drop() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, rgstr) = urio::new(8)?;
+    let (mut tx, rx) = UnixStream::pair()?;
+    let mut buf_ring = rgstr.register_buf_ring(BGID, 1, 16, BufRingFlags::empty())?;
+
+    tx.write_all(b"ping")?;
+    sq.alloc_sqe()?
+        .packup_recv_select(rx.as_raw_fd(), &buf_ring, MsgFlags::empty());
+    sq.submit_and_wait(1)?;
+    let buf = buf_ring.take(&cq.reap_cqe()?).unwrap();
+    assert_eq!(buf.id(), 0);
+
+    // The buffer owned by the `RingBuf` can't be accessed by the ring.
+    assert!(buf_ring.buffer(0, 0, 4).is_none());
+    drop(buf);
+    assert_eq!(buf_ring.buffer(0, 0, 4), Some(&b"ping"[..]));
+    buf_ring.recycle(0);
+
+    Ok(())
+}
+
+#[test]
+fn buf_ring_drop_unregisters() -> Result<(), Box<dyn Error>> {
+    let (_, _, rgstr) = urio::new(8)?;
+
+    let buf_ring = rgstr.register_buf_ring(BGID, 4, 16, BufRingFlags::empty())?;
+    assert!(rgstr
+        .register_buf_ring(BGID, 4, 16, BufRingFlags::empty())
+        .is_err());
+    drop(buf_ring);
+    rgstr.register_buf_ring(BGID, 4, 16, BufRingFlags::empty())?;
+
+    Ok(())
+}
+
+#[test]
+fn buf_ring_drop_pending() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, rgstr) = urio::new(8)?;
+    let (mut tx, rx) = UnixStream::pair()?;
+    let buf_ring = rgstr.register_buf_ring(BGID, 2, 16, BufRingFlags::empty())?;
+
+    // The buffers may be written by the pending operation, so they are
+    // leaked.
+    sq.alloc_sqe()?
+        .packup_recv_select(rx.as_raw_fd(), &buf_ring, MsgFlags::empty());
+    sq.submit()?;
+    drop(buf_ring);
+
+    // No buffer is left to be selected once the ring is unregistered.
+    tx.write_all(b"ping")?;
+    sq.submit_and_wait(1)?;
+    let err = cq.reap_cqe()?.result().unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ENOBUFS));
+
+    Ok(())
+}
+
+#[test]
+fn buf_ring_tracks_buffers() -> Result<(), Box<dyn Error>> {
+    let (_, _, rgstr) = urio::new(8)?;
+    let mut buf_ring = rgstr.register_buf_ring_empty(BGID, 2, 16, BufRingFlags::empty())?;
+
+    buf_ring.buffer_mut(0).unwrap().copy_from_slice(&[1; 16]);
+    buf_ring.push(0);
+    // The buffer in the ring can't be accessed.
+    assert!(buf_ring.buffer_mut(0).is_none());
+    assert!(buf_ring.buffer(0, 0, 16).is_none());
+    assert_eq!(buf_ring.buffer(1, 0, 16), Some(&[0; 16][..]));
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "already in the ring")]
+fn buf_ring_push_twice() {
+    let (_, _, rgstr) = urio::new(8).unwrap();
+    let mut buf_ring = rgstr
+        .register_buf_ring(BGID, 2, 16, BufRingFlags::empty())
+        .unwrap();
+    buf_ring.push(0);
+}
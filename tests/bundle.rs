@@ -0,0 +1,107 @@
+use std::{
+    error::Error,
+    io::{Read, Write},
+    os::unix::{io::AsRawFd, net::UnixStream},
+};
+
+use urio::{op::MsgFlags, BufRingFlags};
+
+const BGID: u16 = 9;
+const TEXT: &[u8] = b"Lorem ipsum dolor sit amet";
+
+#[test]
+fn recv_bundle() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, rgstr) = urio::new(8)?;
+    let (mut tx, rx) = UnixStream::pair()?;
+    let mut buf_ring = rgstr.register_buf_ring(BGID, 8, 8, BufRingFlags::empty())?;
+    tx.write_all(TEXT)?;
+
+    // The kernel may not receive all the data at once.
+    let mut received = Vec::new();
+    while received.len() < TEXT.len() {
+        sq.alloc_sqe()?
+            .packup_recv_bundle(rx.as_raw_fd(), &buf_ring, MsgFlags::empty());
+        let submitted = sq.submit_and_wait(1)?;
+        assert_eq!(submitted, 1);
+
+        let cqe = cq.reap_cqe()?;
+        let len = cqe.result()? as usize;
+        let (bids, data): (Vec<_>, Vec<_>) = buf_ring.bundle(&cqe).unzip();
+        assert_eq!(bids.len(), len.div_ceil(8));
+        assert!(data.iter().rev().skip(1).all(|data| data.len() == 8));
+        received.extend(data.concat());
+
+        for bid in bids {
+            buf_ring.push(bid);
+        }
+        buf_ring.commit();
+    }
+    assert_eq!(received, TEXT);
+
+    Ok(())
+}
+
+#[test]
+fn recv_multi_bundle() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, rgstr) = urio::new(8)?;
+    let (mut tx, rx) = UnixStream::pair()?;
+    let mut buf_ring = rgstr.register_buf_ring(BGID, 8, 8, BufRingFlags::empty())?;
+
+    sq.alloc_sqe()?
+        .packup_recv_multi_bundle(rx.as_raw_fd(), &buf_ring, MsgFlags::empty());
+    let submitted = sq.submit()?;
+    assert_eq!(submitted, 1);
+
+    let mut received = Vec::new();
+    for _ in 0..3 {
+        tx.write_all(TEXT)?;
+        while received.len() < TEXT.len() {
+            sq.submit_and_wait(1)?;
+            let cqe = cq.reap_cqe()?;
+            assert!(cqe.has_more());
+
+            let (bids, data): (Vec<_>, Vec<_>) = buf_ring.bundle(&cqe).unzip();
+            received.extend(data.concat());
+            for bid in bids {
+                buf_ring.push(bid);
+            }
+            buf_ring.commit();
+        }
+        assert_eq!(received, TEXT);
+        received.clear();
+    }
+
+    Ok(())
+}
+
+#[test]
+fn send_bundle() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, rgstr) = urio::new(8)?;
+    let (tx, mut rx) = UnixStream::pair()?;
+    let mut buf_ring = rgstr.register_buf_ring_empty(BGID, 4, 16, BufRingFlags::empty())?;
+
+    for (bid, chunk) in TEXT.chunks(10).enumerate() {
+        buf_ring.buffer_mut(bid as _).unwrap()[..chunk.len()].copy_from_slice(chunk);
+        buf_ring.push_with_len(bid as _, chunk.len());
+    }
+    buf_ring.commit();
+
+    sq.alloc_sqe()?
+        .packup_send_bundle(tx.as_raw_fd(), &buf_ring, MsgFlags::empty());
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+
+    let cqe = cq.reap_cqe()?;
+    assert_eq!(cqe.result()?, TEXT.len() as _);
+    let bids = buf_ring
+        .bundle(&cqe)
+        .map(|(bid, _)| bid)
+        .collect::<Vec<_>>();
+    assert_eq!(bids, [0, 1, 2]);
+
+    let mut buf = [0; TEXT.len()];
+    rx.read_exact(&mut buf)?;
+    assert_eq!(&buf[..], TEXT);
+
+    Ok(())
+}
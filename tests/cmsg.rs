@@ -0,0 +1,123 @@
+use std::{
+    error::Error,
+    io::{self, IoSlice, IoSliceMut},
+    mem,
+    net::{TcpListener, TcpStream},
+    os::unix::{io::AsRawFd, net::UnixStream},
+};
+
+use urio::op::{CmsgBuf, ControlMessage, Credentials, MsgFlags, MsgHdr};
+
+#[test]
+fn scm_rights() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let (tx, rx) = UnixStream::pair()?;
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+
+    let mut cmsgs = CmsgBuf::new();
+    cmsgs.push_rights(&[listener.as_raw_fd()]);
+    let bufs = [IoSlice::new(b"fd")];
+    let mut msg = MsgHdr::new();
+    msg.iovecs(&bufs).cmsgs(&cmsgs);
+    // The `msg` outlives the operation.
+    unsafe {
+        sq.alloc_sqe()?
+            .packup_sendmsg(tx.as_raw_fd(), &msg, MsgFlags::empty())
+    };
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    cq.reap_cqe()?.result()?;
+
+    let mut cmsgs = CmsgBuf::with_capacity(CmsgBuf::space_for_rights(1));
+    let mut buf = [0; 2];
+    let mut bufs = [IoSliceMut::new(&mut buf)];
+    let mut msg = MsgHdr::new();
+    msg.iovecs_mut(&mut bufs).cmsgs_mut(&mut cmsgs);
+    unsafe {
+        sq.alloc_sqe()?
+            .packup_recvmsg(rx.as_raw_fd(), &mut msg, MsgFlags::CMSG_CLOEXEC)
+    };
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    assert_eq!(cq.reap_cqe()?.result()?, 2);
+    let len = msg.control_len();
+
+    // The buffer is filled by the recvmsg above.
+    let mut msgs = unsafe { cmsgs.decode(len) };
+    assert_eq!(msgs.len(), 1);
+    let fd = match msgs.pop() {
+        Some(ControlMessage::ScmRights(mut fds)) if fds.len() == 1 => fds.pop().unwrap(),
+        msg => panic!("unexpected control message: {:?}", msg),
+    };
+    assert_ne!(fd.as_raw_fd(), listener.as_raw_fd());
+    assert!(unsafe { cmsgs.decode(len) }.is_empty());
+
+    // The received fd refers to the same listening socket.
+    let received = TcpListener::from(fd);
+    assert_eq!(received.local_addr()?, listener.local_addr()?);
+    let client = TcpStream::connect(received.local_addr()?)?;
+    let (_, peer) = received.accept()?;
+    assert_eq!(peer, client.local_addr()?);
+
+    Ok(())
+}
+
+#[test]
+fn scm_credentials() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let (tx, rx) = UnixStream::pair()?;
+    set_passcred(&rx)?;
+
+    let mut cmsgs = CmsgBuf::new();
+    cmsgs.push_credentials(Credentials::current());
+    let bufs = [IoSlice::new(b"cred")];
+    let mut msg = MsgHdr::new();
+    msg.iovecs(&bufs).cmsgs(&cmsgs);
+    unsafe {
+        sq.alloc_sqe()?
+            .packup_sendmsg(tx.as_raw_fd(), &msg, MsgFlags::empty())
+    };
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    cq.reap_cqe()?.result()?;
+
+    let mut cmsgs = CmsgBuf::with_capacity(CmsgBuf::space_for_credentials());
+    let mut buf = [0; 4];
+    let mut bufs = [IoSliceMut::new(&mut buf)];
+    let mut msg = MsgHdr::new();
+    msg.iovecs_mut(&mut bufs).cmsgs_mut(&mut cmsgs);
+    unsafe {
+        sq.alloc_sqe()?
+            .packup_recvmsg(rx.as_raw_fd(), &mut msg, MsgFlags::empty())
+    };
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    assert_eq!(cq.reap_cqe()?.result()?, 4);
+    assert!(!msg.msg_flags().contains(MsgFlags::CTRUNC));
+    let len = msg.control_len();
+
+    // The buffer is filled by the recvmsg above.
+    match unsafe { cmsgs.decode(len) }.as_slice() {
+        [ControlMessage::ScmCredentials(cred)] => assert_eq!(*cred, Credentials::current()),
+        msgs => panic!("unexpected control messages: {:?}", msgs),
+    }
+
+    Ok(())
+}
+
+fn set_passcred(sock: &UnixStream) -> io::Result<()> {
+    let on: libc::c_int = 1;
+    let ret = unsafe {
+        libc::setsockopt(
+            sock.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_PASSCRED,
+            &on as *const _ as _,
+            mem::size_of_val(&on) as _,
+        )
+    };
+    match ret {
+        -1 => Err(io::Error::last_os_error()),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,27 @@
+// Each test crate uses only some of the fixtures.
+#![allow(dead_code)]
+
+use std::{
+    fs::File,
+    io,
+    net::{TcpListener, TcpStream},
+    os::unix::io::{FromRawFd, RawFd},
+};
+
+/// Create a connected pair of TCP sockets on the loopback, the client and the
+/// server side of the connection.
+pub fn tcp_pair() -> io::Result<(TcpStream, TcpStream)> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let client = TcpStream::connect(listener.local_addr()?)?;
+    let (server, _) = listener.accept()?;
+    Ok((client, server))
+}
+
+/// Create a pipe, the read and the write end of it.
+pub fn pipe() -> io::Result<(File, File)> {
+    let mut fds: [RawFd; 2] = [0; 2];
+    if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(unsafe { (File::from_raw_fd(fds[0]), File::from_raw_fd(fds[1])) })
+}
@@ -0,0 +1,134 @@
+use std::{
+    error::Error,
+    fs::File,
+    io::{self, Write},
+    os::unix::{
+        io::{AsRawFd, FromRawFd},
+        net::UnixStream,
+    },
+};
+
+use urio::op::{EpollEvent, EpollFlags, EpollOp};
+
+fn epoll_create() -> io::Result<File> {
+    let fd = unsafe { libc::epoll_create1(libc::EPOLL_CLOEXEC) };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(unsafe { File::from_raw_fd(fd) })
+}
+
+/// Returns the events of the file descriptors in the epoll instance which are
+/// currently ready, using epoll_wait(2) without blocking.
+fn ready_events(epoll: &File) -> io::Result<Vec<(EpollFlags, u64)>> {
+    let mut events = [EpollEvent::empty(); 8];
+    let n = unsafe { libc::epoll_wait(epoll.as_raw_fd(), events.as_mut_ptr() as _, 8, 0) };
+    if n < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(events[..n as usize]
+        .iter()
+        .map(|e| (e.events(), e.data()))
+        .collect())
+}
+
+#[test]
+fn epoll_ctl() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let epoll = epoll_create()?;
+    let (a, mut b) = UnixStream::pair()?;
+
+    let event = EpollEvent::new(EpollFlags::IN, 42);
+    sq.alloc_sqe()?
+        .packup_epoll_ctl(epoll.as_raw_fd(), EpollOp::Add, a.as_raw_fd(), event);
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    cq.reap_cqe()?.result()?;
+    assert!(ready_events(&epoll)?.is_empty());
+
+    b.write_all(b"ping")?;
+    assert_eq!(ready_events(&epoll)?, [(EpollFlags::IN, 42)]);
+
+    let event = EpollEvent::new(EpollFlags::IN | EpollFlags::OUT, 7);
+    sq.alloc_sqe()?
+        .packup_epoll_ctl(epoll.as_raw_fd(), EpollOp::Modify, a.as_raw_fd(), event);
+    sq.submit_and_wait(1)?;
+    cq.reap_cqe()?.result()?;
+    assert_eq!(
+        ready_events(&epoll)?,
+        [(EpollFlags::IN | EpollFlags::OUT, 7)]
+    );
+
+    sq.alloc_sqe()?.packup_epoll_ctl(
+        epoll.as_raw_fd(),
+        EpollOp::Delete,
+        a.as_raw_fd(),
+        EpollEvent::empty(),
+    );
+    sq.submit_and_wait(1)?;
+    cq.reap_cqe()?.result()?;
+    assert!(ready_events(&epoll)?.is_empty());
+
+    // It's no longer in the interest list.
+    sq.alloc_sqe()?.packup_epoll_ctl(
+        epoll.as_raw_fd(),
+        EpollOp::Delete,
+        a.as_raw_fd(),
+        EpollEvent::empty(),
+    );
+    sq.submit_and_wait(1)?;
+    let err = cq.reap_cqe()?.result().unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ENOENT));
+
+    Ok(())
+}
+
+#[test]
+fn epoll_wait() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let epoll = epoll_create()?;
+    let (a, mut b) = UnixStream::pair()?;
+    let (c, mut d) = UnixStream::pair()?;
+
+    let events = [
+        (a.as_raw_fd(), EpollEvent::new(EpollFlags::IN, 1)),
+        (c.as_raw_fd(), EpollEvent::new(EpollFlags::IN, 2)),
+    ];
+    for (fd, event) in events {
+        sq.alloc_sqe()?
+            .packup_epoll_ctl(epoll.as_raw_fd(), EpollOp::Add, fd, event);
+    }
+    sq.submit_and_wait(2)?;
+    for _ in 0..2 {
+        cq.reap_cqe()?.result()?;
+    }
+
+    // Nothing is ready, so the wait is pending until a write.
+    sq.alloc_sqe()?
+        .packup_epoll_wait(epoll.as_raw_fd(), Vec::with_capacity(4));
+    sq.submit()?;
+    assert!(cq.is_empty());
+
+    d.write_all(b"ping")?;
+    sq.submit_and_wait(1)?;
+    let mut cqe = cq.reap_cqe()?;
+    assert_eq!(cqe.result()?, 1);
+    let events = cqe.take_buf::<Vec<EpollEvent>>().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].events(), EpollFlags::IN);
+    assert_eq!(events[0].data(), 2);
+
+    // The events are filled from the start again.
+    b.write_all(b"pong")?;
+    sq.alloc_sqe()?.packup_epoll_wait(epoll.as_raw_fd(), events);
+    sq.submit_and_wait(1)?;
+    let mut cqe = cq.reap_cqe()?;
+    assert_eq!(cqe.result()?, 2);
+    let events = cqe.take_buf::<Vec<EpollEvent>>().unwrap();
+    assert_eq!(events.len(), 2);
+    let mut data: Vec<_> = events.iter().map(|e| e.data()).collect();
+    data.sort_unstable();
+    assert_eq!(data, [1, 2]);
+
+    Ok(())
+}
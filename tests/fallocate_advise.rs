@@ -0,0 +1,143 @@
+use std::{
+    error::Error,
+    io::Write,
+    os::unix::{fs::MetadataExt, io::AsRawFd},
+    ptr,
+};
+
+use tempfile::tempfile;
+use urio::op::{Advice, FallocateMode, SyncRangeFlags};
+
+const LEN: u64 = 64 * 1024;
+
+#[test]
+fn fallocate() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let file = tempfile()?;
+
+    sq.alloc_sqe()?
+        .packup_fallocate(file.as_raw_fd(), 0, LEN, FallocateMode::ALLOCATE);
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    cq.reap_cqe()?.result()?;
+    let metadata = file.metadata()?;
+    assert_eq!(metadata.len(), LEN);
+    assert!(metadata.blocks() * 512 >= LEN);
+
+    // Preallocate beyond the end of the file without changing the size.
+    sq.alloc_sqe()?
+        .packup_fallocate(file.as_raw_fd(), LEN, LEN, FallocateMode::KEEP_SIZE);
+    sq.submit_and_wait(1)?;
+    cq.reap_cqe()?.result()?;
+    let metadata = file.metadata()?;
+    assert_eq!(metadata.len(), LEN);
+    assert!(metadata.blocks() * 512 >= 2 * LEN);
+
+    Ok(())
+}
+
+#[test]
+fn fallocate_punch_hole() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let mut file = tempfile()?;
+    file.write_all(&[0xff; LEN as usize])?;
+    file.sync_all()?;
+
+    sq.alloc_sqe()?.packup_fallocate(
+        file.as_raw_fd(),
+        0,
+        LEN,
+        FallocateMode::PUNCH_HOLE | FallocateMode::KEEP_SIZE,
+    );
+    sq.submit_and_wait(1)?;
+    match cq.reap_cqe()?.result() {
+        // The filesystem of the temporary directory may not support it.
+        Err(e) if e.raw_os_error() == Some(libc::EOPNOTSUPP) => return Ok(()),
+        res => res?,
+    };
+    let metadata = file.metadata()?;
+    assert_eq!(metadata.len(), LEN);
+    assert!(metadata.blocks() * 512 < LEN);
+
+    Ok(())
+}
+
+#[test]
+fn sync_file_range() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let mut file = tempfile()?;
+    file.write_all(b"Hello, io_uring!")?;
+
+    sq.alloc_sqe()?.packup_sync_file_range(
+        file.as_raw_fd(),
+        0,
+        0,
+        SyncRangeFlags::WAIT_BEFORE | SyncRangeFlags::WRITE | SyncRangeFlags::WAIT_AFTER,
+    );
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    assert_eq!(cq.reap_cqe()?.result()?, 0);
+
+    Ok(())
+}
+
+#[test]
+fn fadvise() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let mut file = tempfile()?;
+    file.write_all(&[0; LEN as usize])?;
+
+    for advice in [Advice::SEQUENTIAL, Advice::WILLNEED, Advice::DONTNEED] {
+        sq.alloc_sqe()?
+            .packup_fadvise(file.as_raw_fd(), 0, LEN as _, advice);
+    }
+    let submitted = sq.submit_and_wait(3)?;
+    assert_eq!(submitted, 3);
+    for _ in 0..3 {
+        assert_eq!(cq.reap_cqe()?.result()?, 0);
+    }
+
+    // An unknown advice is rejected.
+    sq.alloc_sqe()?
+        .packup_fadvise(file.as_raw_fd(), 0, 0, Advice::from(-1));
+    sq.submit_and_wait(1)?;
+    let err = cq.reap_cqe()?.result().unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EINVAL));
+
+    Ok(())
+}
+
+#[test]
+fn madvise() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let len = LEN as usize;
+    let addr = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            len,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+    assert_ne!(addr, libc::MAP_FAILED);
+    unsafe { ptr::write_bytes(addr as *mut u8, 0xff, len) };
+
+    unsafe {
+        sq.alloc_sqe()?
+            .packup_madvise(addr, len as _, Advice::DONTNEED)
+    };
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    let res = cq.reap_cqe()?.result();
+    // The private anonymous pages are zero-filled on the next access.
+    let zeroed = unsafe { std::slice::from_raw_parts(addr as *const u8, len) }
+        .iter()
+        .all(|&b| b == 0);
+    unsafe { libc::munmap(addr, len) };
+    res?;
+    assert!(zeroed);
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use std::{
+    error::Error,
+    fs::File,
+    io::{Read, Seek, SeekFrom, Write},
+    net::{SocketAddr, UdpSocket},
+    os::unix::io::{AsRawFd, OwnedFd},
+};
+
+use tempfile::tempfile;
+use urio::op::{Domain, InstallFlags, Protocol, SockAddrStorage, SockFlags, SockType};
+
+fn is_cloexec(fd: &OwnedFd) -> bool {
+    let flags = unsafe { libc::fcntl(fd.as_raw_fd(), libc::F_GETFD) };
+    assert!(flags >= 0);
+    flags & libc::FD_CLOEXEC != 0
+}
+
+#[test]
+fn fixed_fd_install() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, rgstr) = urio::new(8)?;
+    let mut file = tempfile()?;
+    rgstr.register_files(&[file.as_raw_fd()])?;
+
+    sq.alloc_sqe()?
+        .packup_fixed_fd_install(0, InstallFlags::empty());
+    sq.alloc_sqe()?
+        .packup_fixed_fd_install(0, InstallFlags::NO_CLOEXEC);
+    let submitted = sq.submit_and_wait(2)?;
+    assert_eq!(submitted, 2);
+    let fd = unsafe { cq.reap_cqe()?.into_fd()? };
+    let fd_no_cloexec = unsafe { cq.reap_cqe()?.into_fd()? };
+    assert_ne!(fd.as_raw_fd(), file.as_raw_fd());
+    assert!(is_cloexec(&fd));
+    assert!(!is_cloexec(&fd_no_cloexec));
+
+    // The new descriptor refers to the same file.
+    File::from(fd).write_all(b"Hello, io_uring!")?;
+    let mut buf = String::new();
+    file.seek(SeekFrom::Start(0))?;
+    file.read_to_string(&mut buf)?;
+    assert_eq!(buf, "Hello, io_uring!");
+
+    // Not a fixed file.
+    sq.alloc_sqe()?
+        .packup_fixed_fd_install(1, InstallFlags::empty());
+    sq.submit_and_wait(1)?;
+    let err = unsafe { cq.reap_cqe()?.into_fd() }.unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EBADF));
+
+    Ok(())
+}
+
+#[test]
+fn fixed_fd_install_direct_socket() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, rgstr) = urio::new(8)?;
+    rgstr.register_files_sparse(4)?;
+
+    sq.alloc_sqe()?.packup_socket_direct(
+        Domain::INET,
+        SockType::DGRAM,
+        Protocol::UDP,
+        SockFlags::empty(),
+        Some(2),
+    );
+    sq.submit_and_wait(1)?;
+    cq.reap_cqe()?.result()?;
+
+    sq.alloc_sqe()?
+        .packup_fixed_fd_install(2, InstallFlags::empty());
+    sq.submit_and_wait(1)?;
+    let fd = unsafe { cq.reap_cqe()?.into_fd()? };
+
+    let addr = SockAddrStorage::from("127.0.0.1:0".parse::<SocketAddr>()?);
+    sq.alloc_sqe()?.packup_bind(fd.as_raw_fd(), addr);
+    sq.submit_and_wait(1)?;
+    cq.reap_cqe()?.result()?;
+
+    // It's a regular socket which works with the standard library.
+    let socket = UdpSocket::from(fd);
+    let peer = UdpSocket::bind("127.0.0.1:0")?;
+    peer.send_to(b"ping", socket.local_addr()?)?;
+    let mut buf = [0; 4];
+    let (n, addr) = socket.recv_from(&mut buf)?;
+    assert_eq!(&buf[..n], b"ping");
+    assert_eq!(addr, peer.local_addr()?);
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use std::{error::Error, io::Write, os::unix::io::AsRawFd};
+
+use tempfile::tempfile;
+
+#[test]
+fn ftruncate() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let mut file = tempfile()?;
+    file.write_all(b"Hello, io_uring!")?;
+
+    sq.alloc_sqe()?.packup_ftruncate(file.as_raw_fd(), 5);
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    assert_eq!(cq.reap_cqe()?.result()?, 0);
+    assert_eq!(file.metadata()?.len(), 5);
+
+    // Extend it with zeros.
+    sq.alloc_sqe()?.packup_ftruncate(file.as_raw_fd(), 4096);
+    sq.submit_and_wait(1)?;
+    cq.reap_cqe()?.result()?;
+    assert_eq!(file.metadata()?.len(), 4096);
+
+    Ok(())
+}
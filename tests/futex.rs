@@ -0,0 +1,124 @@
+use std::{
+    error::Error,
+    sync::{atomic::Ordering, Arc},
+};
+
+use urio::op::{Futex, FutexFlags, FutexWaitV};
+
+#[test]
+fn futex_wait_mismatch() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let futex = Arc::new(Futex::new(1));
+
+    sq.alloc_sqe()?
+        .packup_futex_wait(futex, 0, Futex::MATCH_ANY, FutexFlags::PRIVATE);
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    let err = cq.reap_cqe()?.result().unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EAGAIN));
+
+    Ok(())
+}
+
+#[test]
+fn futex_wait_wake() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let futex = Arc::new(Futex::new(0));
+
+    sq.alloc_sqe()?
+        .user_data(1)
+        .packup_futex_wait(futex.clone(), 0, 0b01, FutexFlags::PRIVATE);
+    sq.submit()?;
+    assert!(cq.is_empty());
+
+    futex.store(1, Ordering::Release);
+    // A waker whose bitmask does not intersect wakes nobody.
+    sq.alloc_sqe()?
+        .user_data(2)
+        .packup_futex_wake(futex.clone(), 1, 0b10, FutexFlags::PRIVATE);
+    sq.submit_and_wait(1)?;
+    let cqe = cq.reap_cqe()?;
+    assert_eq!(cqe.user_data(), 2);
+    assert_eq!(cqe.result()?, 0);
+    assert!(cq.is_empty());
+
+    sq.alloc_sqe()?
+        .user_data(2)
+        .packup_futex_wake(futex, 1, Futex::MATCH_ANY, FutexFlags::PRIVATE);
+    sq.submit_and_wait(2)?;
+    for cqe in cq.reap_cqes(2)? {
+        match cqe.user_data() {
+            1 => assert_eq!(cqe.result()?, 0),
+            // The number of woken waiters.
+            2 => assert_eq!(cqe.result()?, 1),
+            _ => unreachable!(),
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn futex_wake_from_thread() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let futex = Arc::new(Futex::new(0));
+
+    sq.alloc_sqe()?
+        .packup_futex_wait(futex.clone(), 0, Futex::MATCH_ANY, FutexFlags::PRIVATE);
+
+    let handle = std::thread::spawn(move || loop {
+        // FUTEX_WAKE_PRIVATE with futex(2), until the waiter is woken.
+        let woken = unsafe {
+            libc::syscall(
+                libc::SYS_futex,
+                futex.as_ptr(),
+                libc::FUTEX_WAKE | libc::FUTEX_PRIVATE_FLAG,
+                1,
+            )
+        };
+        if woken == 1 {
+            break;
+        }
+        std::thread::yield_now();
+    });
+
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    assert_eq!(cq.reap_cqe()?.result()?, 0);
+    handle.join().unwrap();
+
+    Ok(())
+}
+
+#[test]
+fn futex_waitv() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let futexes: Vec<_> = (0..3).map(|_| Arc::new(Futex::new(0))).collect();
+
+    let waitv = futexes
+        .iter()
+        .map(|futex| FutexWaitV::new(futex.clone(), 0, FutexFlags::PRIVATE))
+        .collect();
+    sq.alloc_sqe()?.user_data(1).packup_futex_waitv(waitv);
+    sq.submit()?;
+    assert!(cq.is_empty());
+
+    futexes[2].store(1, Ordering::Release);
+    sq.alloc_sqe()?.user_data(2).packup_futex_wake(
+        futexes[2].clone(),
+        1,
+        Futex::MATCH_ANY,
+        FutexFlags::PRIVATE,
+    );
+    sq.submit_and_wait(2)?;
+    for cqe in cq.reap_cqes(2)? {
+        match cqe.user_data() {
+            // The index of the woken futex.
+            1 => assert_eq!(cqe.result()?, 2),
+            2 => assert_eq!(cqe.result()?, 1),
+            _ => unreachable!(),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,123 @@
+mod common;
+
+use std::{
+    error::Error,
+    io::{Read, Write},
+    os::unix::io::AsRawFd,
+    time::{Duration, Instant},
+};
+
+use urio::op::{Fd, SpliceFlags};
+
+use common::pipe;
+
+const TEXT: &[u8] = b"Hello, io_uring!";
+
+#[test]
+fn msg_ring() -> Result<(), Box<dyn Error>> {
+    let (mut sq1, mut cq1, _) = urio::new(8)?;
+    let (sq2, mut cq2, _) = urio::new(8)?;
+
+    sq1.alloc_sqe()?
+        .user_data(1)
+        .packup_msg_ring(sq2.uring(), 42, 0xdead_beef);
+    let submitted = sq1.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+
+    let cqe = cq1.reap_cqe()?;
+    assert_eq!(cqe.user_data(), 1);
+    assert_eq!(cqe.result()?, 0);
+
+    // The target ring receives the message without submitting anything.
+    let cqe = cq2.reap_cqe()?;
+    assert_eq!(cqe.user_data(), 0xdead_beef);
+    assert_eq!(cqe.result()?, 42);
+
+    Ok(())
+}
+
+#[test]
+fn msg_ring_between_threads() -> Result<(), Box<dyn Error>> {
+    let (mut sq1, mut cq1, _) = urio::new(8)?;
+    let (sq2, mut cq2, _) = urio::new(8)?;
+
+    let handle = std::thread::spawn(move || -> Result<u64, &'static str> {
+        // Spin until a message arrives, but not forever.
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while cq2.is_empty() {
+            if Instant::now() > deadline {
+                return Err("no message arrived");
+            }
+            std::thread::yield_now();
+        }
+        let cqe = cq2.reap_cqe()?;
+        Ok(cqe.user_data())
+    });
+
+    sq1.alloc_sqe()?.packup_msg_ring(sq2.uring(), 0, 7);
+    sq1.submit_and_wait(1)?;
+    cq1.reap_cqe()?.result()?;
+    assert_eq!(handle.join().unwrap()?, 7);
+
+    Ok(())
+}
+
+#[test]
+fn msg_ring_send_fd() -> Result<(), Box<dyn Error>> {
+    let (mut sq1, mut cq1, rgstr1) = urio::new(8)?;
+    let (mut sq2, mut cq2, rgstr2) = urio::new(8)?;
+    let (rx, mut tx) = pipe()?;
+    rgstr1.register_files(&[rx.as_raw_fd()])?;
+    rgstr2.register_files_sparse(4)?;
+    drop(rx);
+
+    sq1.alloc_sqe()?
+        .packup_msg_ring_send_fd(sq2.uring(), 0, None, 1);
+    sq1.alloc_sqe()?
+        .packup_msg_ring_send_fd(sq2.uring(), 0, Some(3), 2);
+    let submitted = sq1.submit_and_wait(2)?;
+    assert_eq!(submitted, 2);
+    for cqe in cq1.reap_cqes(2)? {
+        assert_eq!(cqe.result()?, 0);
+    }
+
+    let mut slots = Vec::new();
+    for cqe in cq2.reap_cqes(2)? {
+        let slot = match cqe.user_data() {
+            // The first free slot is allocated.
+            1 => cqe.result()?,
+            // The result is zero if the slot is given.
+            2 => {
+                assert_eq!(cqe.result()?, 0);
+                3
+            }
+            _ => unreachable!(),
+        };
+        slots.push(slot);
+    }
+    slots.sort_unstable();
+    assert_eq!(slots, [0, 3]);
+
+    // Both slots of the target ring refer to the read end of the pipe.
+    tx.write_all(TEXT)?;
+    let (mut out_rx, out_tx) = pipe()?;
+    for slot in slots {
+        sq2.alloc_sqe()?.packup_splice(
+            Fd::Fixed(slot),
+            None,
+            out_tx.as_raw_fd(),
+            None,
+            (TEXT.len() / 2) as _,
+            SpliceFlags::empty(),
+        );
+        sq2.submit_and_wait(1)?;
+        assert_eq!(cq2.reap_cqe()?.result()?, (TEXT.len() / 2) as u32);
+    }
+    drop(out_tx);
+
+    let mut buf = Vec::new();
+    out_rx.read_to_end(&mut buf)?;
+    assert_eq!(buf, TEXT);
+
+    Ok(())
+}
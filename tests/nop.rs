@@ -13,3 +13,28 @@ fn nop_single() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+#[test]
+fn flags_replaced() -> Result<(), Box<dyn Error>> {
+    const IOSQE_IO_LINK: u8 = 1 << 2;
+    let (mut sq, mut cq, _) = urio::new(8)?;
+
+    // The link flag is cleared, so the failure doesn't cancel the nop.
+    sq.alloc_sqe()?
+        .user_data(1)
+        .flags(IOSQE_IO_LINK)
+        .flags(0)
+        .packup_close(-1);
+    sq.alloc_sqe()?.user_data(2).packup_nop();
+    sq.submit_and_wait(2)?;
+
+    for cqe in cq.reap_cqes(2)? {
+        match cqe.user_data() {
+            1 => assert!(cqe.result().is_err()),
+            2 => assert_eq!(cqe.result()?, 0),
+            _ => unreachable!(),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,155 @@
+use std::{
+    error::Error,
+    ffi::CString,
+    fs::{self, File},
+    io::Read,
+    os::unix::{
+        ffi::OsStrExt,
+        fs::MetadataExt,
+        io::{AsRawFd, FromRawFd, IntoRawFd},
+    },
+};
+
+use tempfile::tempdir;
+use urio::op::{AtFlags, OFlag, OpenHow, ResolveFlags, Statx, StatxMask};
+
+const TEXT: &[u8] = b"Hello, io_uring!";
+
+#[test]
+fn openat_close() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let dir = tempdir()?;
+    let path = dir.path().join("file");
+    fs::write(&path, TEXT)?;
+    let cpath = CString::new(path.as_os_str().as_bytes())?;
+
+    sq.alloc_sqe()?
+        .packup_openat(libc::AT_FDCWD, cpath, OFlag::RDONLY, 0);
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    let fd = cq.reap_cqe()?.result()?;
+
+    let mut file = unsafe { File::from_raw_fd(fd as _) };
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf)?;
+    assert_eq!(buf, TEXT);
+    let fd = file.into_raw_fd();
+
+    sq.alloc_sqe()?.packup_close(fd);
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    cq.reap_cqe()?.result()?;
+
+    // The descriptor is no longer valid.
+    sq.alloc_sqe()?.packup_close(fd);
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    let err = cq.reap_cqe()?.result().unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EBADF));
+
+    Ok(())
+}
+
+#[test]
+fn openat_create() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let dir = tempdir()?;
+    let path = dir.path().join("new");
+    let cpath = CString::new(path.as_os_str().as_bytes())?;
+
+    let flags = OFlag::WRONLY | OFlag::CREAT | OFlag::EXCL | OFlag::CLOEXEC;
+    sq.alloc_sqe()?
+        .packup_openat(libc::AT_FDCWD, cpath.clone(), flags, 0o600);
+    sq.submit_and_wait(1)?;
+    let file = unsafe { File::from_raw_fd(cq.reap_cqe()?.result()? as _) };
+    assert_eq!(file.metadata()?.mode() & 0o777, 0o600);
+
+    // Creating it again fails because of `EXCL`.
+    sq.alloc_sqe()?
+        .packup_openat(libc::AT_FDCWD, cpath, flags, 0o600);
+    sq.submit_and_wait(1)?;
+    let err = cq.reap_cqe()?.result().unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EEXIST));
+
+    Ok(())
+}
+
+#[test]
+fn openat2_resolve_beneath() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let dir = tempdir()?;
+    fs::write(dir.path().join("inner"), TEXT)?;
+    let dirfile = File::open(dir.path())?;
+    let dirfd = dirfile.as_raw_fd();
+
+    let mut how = OpenHow::new(OFlag::RDONLY | OFlag::CLOEXEC);
+    how.resolve(ResolveFlags::BENEATH);
+
+    let inner = CString::new("inner")?;
+    sq.alloc_sqe()?.packup_openat2(dirfd, inner, how);
+    sq.submit_and_wait(1)?;
+    let mut file = unsafe { File::from_raw_fd(cq.reap_cqe()?.result()? as _) };
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf)?;
+    assert_eq!(buf, TEXT);
+
+    let outer = CString::new("../inner")?;
+    sq.alloc_sqe()?.packup_openat2(dirfd, outer, how);
+    sq.submit_and_wait(1)?;
+    let err = cq.reap_cqe()?.result().unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EXDEV));
+
+    Ok(())
+}
+
+#[test]
+fn statx() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let dir = tempdir()?;
+    let path = dir.path().join("file");
+    fs::write(&path, TEXT)?;
+    let cpath = CString::new(path.as_os_str().as_bytes())?;
+
+    sq.alloc_sqe()?.packup_statx(
+        libc::AT_FDCWD,
+        cpath,
+        AtFlags::empty(),
+        StatxMask::BASIC_STATS,
+        Box::new(Statx::new()),
+    );
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    let mut cqe = cq.reap_cqe()?;
+    cqe.result()?;
+    let statx = cqe.take_buf::<Box<Statx>>().unwrap();
+
+    let metadata = fs::metadata(&path)?;
+    assert!(statx.mask().contains(StatxMask::SIZE | StatxMask::INO));
+    assert_eq!(statx.size(), TEXT.len() as u64);
+    assert_eq!(statx.ino(), metadata.ino());
+    assert_eq!(statx.mode(), metadata.mode());
+    assert_eq!(statx.nlink(), 1);
+    assert_eq!(statx.mtime().as_secs() as i64, metadata.mtime());
+
+    Ok(())
+}
+
+#[test]
+fn statx_empty_path() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let file = tempfile::tempfile()?;
+    let fd = file.as_raw_fd();
+
+    let empty = CString::new("")?;
+    let statx = Box::new(Statx::default());
+    sq.alloc_sqe()?
+        .packup_statx(fd, empty, AtFlags::EMPTY_PATH, StatxMask::SIZE, statx);
+    sq.submit_and_wait(1)?;
+    let mut cqe = cq.reap_cqe()?;
+    cqe.result()?;
+    let statx = cqe.take_buf::<Box<Statx>>().unwrap();
+    assert_eq!(statx.size(), 0);
+    assert!(statx.mask().contains(StatxMask::SIZE));
+
+    Ok(())
+}
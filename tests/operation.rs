@@ -0,0 +1,210 @@
+use std::{
+    error::Error,
+    ffi::CString,
+    fs::{self, File},
+    io::Read as _,
+    os::unix::{io::AsRawFd, net::UnixStream},
+};
+
+use tempfile::{tempdir, NamedTempFile};
+use urio::op::{
+    AtFlags, FGetXattr, FSetXattr, Fsync, FsyncFlags, GetSockOpt, MkdirAt, MsgFlags, Nop,
+    Operation, PollAdd, PollEvent, Read, Recv, RenameAt, RenameFlags, Send, SetSockOpt, UnlinkAt,
+    Write, XattrFlags,
+};
+
+const TEXT: &[u8] = b"Hello, io_uring!";
+const FULL: &str = "Submission Queue is full";
+
+#[test]
+fn push_write_read() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let mut tmpfile = tempfile::tempfile()?;
+    let fd = tmpfile.as_raw_fd();
+
+    let write = Write::new(fd, TEXT, 0);
+    assert_eq!(write.buf, TEXT);
+    sq.push(write).map_err(|_| FULL)?.user_data(1);
+    sq.push(Fsync::new(fd, FsyncFlags::DATASYNC))
+        .map_err(|_| FULL)?
+        .user_data(2);
+    sq.submit_and_wait(2)?;
+
+    for cqe in cq.reap_cqes(2)? {
+        match cqe.user_data() {
+            1 => assert_eq!(cqe.result()? as usize, TEXT.len()),
+            2 => assert_eq!(cqe.result()?, 0),
+            _ => unreachable!(),
+        }
+    }
+    let mut written = Vec::new();
+    tmpfile.read_to_end(&mut written)?;
+    assert_eq!(written, TEXT);
+
+    let mut read = Read::new(fd, Vec::with_capacity(64), 0);
+    // The fields can be changed before it's pushed.
+    read.offset = 7;
+    sq.push(read).map_err(|_| FULL)?.user_data(3);
+    sq.submit_and_wait(1)?;
+
+    let mut cqe = cq.reap_cqe()?;
+    assert_eq!(cqe.user_data(), 3);
+    assert_eq!(cqe.result()? as usize, TEXT.len() - 7);
+    assert_eq!(cqe.take_buf::<Vec<u8>>().unwrap(), &TEXT[7..]);
+
+    Ok(())
+}
+
+#[test]
+fn push_built_elsewhere() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let (tx, rx) = UnixStream::pair()?;
+
+    let ops: Vec<Box<dyn Operation>> = vec![
+        Box::new(PollAdd::new(rx.as_raw_fd(), PollEvent::IN)),
+        Box::new(Send::new(tx.as_raw_fd(), TEXT, MsgFlags::NOSIGNAL)),
+        Box::new(Recv::new(
+            rx.as_raw_fd(),
+            vec![0; 64].into_boxed_slice(),
+            MsgFlags::empty(),
+        )),
+    ];
+
+    for (user_data, op) in ops.into_iter().enumerate() {
+        sq.push(op).map_err(|_| FULL)?.user_data(user_data as _);
+    }
+    sq.submit_and_wait(3)?;
+
+    for mut cqe in cq.reap_cqes(3)? {
+        let res = cqe.result()?;
+        match cqe.user_data() {
+            0 => assert!(PollEvent::from_bits_truncate(res as _).contains(PollEvent::IN)),
+            1 => assert_eq!(res as usize, TEXT.len()),
+            2 => {
+                let buf = cqe.take_buf::<Box<[u8]>>().unwrap();
+                assert_eq!(&buf[..res as usize], TEXT);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn push_again_if_full() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(1)?;
+    let tmpfile = tempfile::tempfile()?;
+
+    sq.push(Nop::new()).map_err(|_| FULL)?.user_data(1);
+    // The operation is handed back when the SQ is full.
+    let write = sq
+        .push(Write::new(tmpfile.as_raw_fd(), TEXT.to_vec(), 0))
+        .err()
+        .unwrap();
+    assert_eq!(write.buf, TEXT);
+    sq.submit_and_wait(1)?;
+    assert_eq!(cq.reap_cqe()?.user_data(), 1);
+
+    sq.push(write).map_err(|_| FULL)?.user_data(2);
+    sq.submit_and_wait(1)?;
+    let cqe = cq.reap_cqe()?;
+    assert_eq!(cqe.user_data(), 2);
+    assert_eq!(cqe.result()? as usize, TEXT.len());
+
+    Ok(())
+}
+
+#[test]
+fn push_owned_paths() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let dir = tempdir()?;
+    let dirfile = File::open(dir.path())?;
+    let dirfd = dirfile.as_raw_fd();
+    let path = |name: &str| CString::new(name).unwrap();
+
+    let ops: Vec<Box<dyn Operation>> = vec![
+        Box::new(MkdirAt::new(dirfd, path("a"), 0o700)),
+        Box::new(RenameAt::new(
+            dirfd,
+            path("a"),
+            dirfd,
+            path("b"),
+            RenameFlags::empty(),
+        )),
+        Box::new(UnlinkAt::new(dirfd, path("b"), AtFlags::REMOVEDIR)),
+    ];
+
+    // They are run one by one, since they depend on each other.
+    for (user_data, op) in ops.into_iter().enumerate() {
+        sq.push(op).map_err(|_| FULL)?.user_data(user_data as _);
+        sq.submit_and_wait(1)?;
+        let cqe = cq.reap_cqe()?;
+        assert_eq!(cqe.user_data(), user_data as _);
+        cqe.result()?;
+
+        match user_data {
+            0 => assert!(fs::metadata(dir.path().join("a"))?.is_dir()),
+            1 => assert!(fs::metadata(dir.path().join("b"))?.is_dir()),
+            _ => assert_eq!(fs::read_dir(dir.path())?.count(), 0),
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn push_owned_xattr() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    // tmpfs supports user extended attributes.
+    let file = NamedTempFile::new_in("/dev/shm")?;
+    let fd = file.as_file().as_raw_fd();
+    let name = || CString::new("user.greeting").unwrap();
+
+    sq.push(FSetXattr::new(fd, name(), TEXT, XattrFlags::CREATE))
+        .map_err(|_| FULL)?;
+    sq.submit_and_wait(1)?;
+    cq.reap_cqe()?.result()?;
+
+    sq.push(FGetXattr::new(fd, name(), Vec::with_capacity(64)))
+        .map_err(|_| FULL)?;
+    sq.submit_and_wait(1)?;
+    let mut cqe = cq.reap_cqe()?;
+    assert_eq!(cqe.result()? as usize, TEXT.len());
+    assert_eq!(cqe.take_buf::<Vec<u8>>().unwrap(), TEXT);
+
+    Ok(())
+}
+
+#[test]
+fn push_owned_sockopt() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let (a, _b) = UnixStream::pair()?;
+    let fd = a.as_raw_fd();
+
+    let optval = 1i32.to_ne_bytes().to_vec();
+    sq.push(SetSockOpt::new(
+        fd,
+        libc::SOL_SOCKET,
+        libc::SO_KEEPALIVE,
+        optval,
+    ))
+    .map_err(|_| FULL)?;
+    sq.submit_and_wait(1)?;
+    cq.reap_cqe()?.result()?;
+
+    let optval = Vec::with_capacity(4);
+    sq.push(GetSockOpt::new(
+        fd,
+        libc::SOL_SOCKET,
+        libc::SO_KEEPALIVE,
+        optval,
+    ))
+    .map_err(|_| FULL)?;
+    sq.submit_and_wait(1)?;
+    let mut cqe = cq.reap_cqe()?;
+    assert_eq!(cqe.result()?, 4);
+    assert_eq!(cqe.take_buf::<Vec<u8>>().unwrap(), 1i32.to_ne_bytes());
+
+    Ok(())
+}
@@ -0,0 +1,134 @@
+use std::{
+    error::Error,
+    io::Read,
+    os::unix::{io::AsRawFd, net::UnixStream},
+};
+
+use urio::op::MsgFlags;
+
+const TEXT: &[u8] = b"Hello, io_uring!";
+
+#[test]
+fn write_read_owned() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let mut tmpfile = tempfile::tempfile()?;
+
+    sq.alloc_sqe()?
+        .user_data(1)
+        .packup_write_owned(tmpfile.as_raw_fd(), TEXT.to_vec(), 0);
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+
+    let mut cqe = cq.reap_cqe()?;
+    assert_eq!(cqe.user_data(), 1);
+    assert_eq!(cqe.result()? as usize, TEXT.len());
+    // The type of the buffer must match.
+    assert_eq!(cqe.take_buf::<Box<[u8]>>(), None);
+    assert_eq!(cqe.take_buf::<Vec<u8>>().unwrap(), TEXT);
+    assert_eq!(cqe.take_buf::<Vec<u8>>(), None);
+
+    let mut written = Vec::new();
+    tmpfile.read_to_end(&mut written)?;
+    assert_eq!(written, TEXT);
+
+    // The `user_data` can be packed after the operation as well.
+    let mut packer = sq.alloc_sqe()?;
+    packer.packup_read_owned(tmpfile.as_raw_fd(), Vec::with_capacity(64), 7);
+    packer.user_data(2);
+    sq.submit_and_wait(1)?;
+
+    let mut cqe = cq.reap_cqe()?;
+    assert_eq!(cqe.user_data(), 2);
+    assert_eq!(cqe.result()? as usize, TEXT.len() - 7);
+    let buf: Vec<u8> = cqe.take_buf().unwrap();
+    assert_eq!(buf, &TEXT[7..]);
+
+    Ok(())
+}
+
+#[test]
+fn send_recv_owned() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let (tx, rx) = UnixStream::pair()?;
+
+    // The buffer is owned by the io_uring instance until the data arrives.
+    sq.alloc_sqe()?.user_data(1).packup_recv_owned(
+        rx.as_raw_fd(),
+        vec![0; 64].into_boxed_slice(),
+        MsgFlags::empty(),
+    );
+    sq.submit()?;
+    assert!(cq.is_empty());
+
+    sq.alloc_sqe()?
+        .user_data(2)
+        .packup_send_owned(tx.as_raw_fd(), TEXT, MsgFlags::NOSIGNAL);
+    sq.submit_and_wait(2)?;
+
+    for mut cqe in cq.reap_cqes(2)? {
+        let len = cqe.result()? as usize;
+        assert_eq!(len, TEXT.len());
+        match cqe.user_data() {
+            1 => {
+                let buf: Box<[u8]> = cqe.take_buf().unwrap();
+                assert_eq!(buf.len(), 64);
+                assert_eq!(&buf[..len], TEXT);
+            }
+            2 => assert_eq!(cqe.take_buf::<&[u8]>().unwrap(), TEXT),
+            _ => unreachable!(),
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn packup_owned_again() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let (tx, rx) = UnixStream::pair()?;
+
+    // The first buffer is dropped since it's never used by the kernel.
+    let mut packer = sq.alloc_sqe()?;
+    packer
+        .user_data(1)
+        .packup_recv_owned(rx.as_raw_fd(), vec![0; 4], MsgFlags::empty());
+    packer.packup_write_owned(tx.as_raw_fd(), TEXT, 0);
+    sq.submit_and_wait(1)?;
+
+    let mut cqe = cq.reap_cqe()?;
+    assert_eq!(cqe.user_data(), 1);
+    assert_eq!(cqe.result()? as usize, TEXT.len());
+    assert_eq!(cqe.take_buf::<Vec<u8>>(), None);
+    assert_eq!(cqe.take_buf::<&[u8]>().unwrap(), TEXT);
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "reserved")]
+fn reserved_user_data() {
+    let (mut sq, _, _) = urio::new(8).unwrap();
+    sq.alloc_sqe().unwrap().user_data(1 << 63);
+}
+
+#[test]
+#[should_panic(expected = "IOSQE_CQE_SKIP_SUCCESS")]
+fn owned_skip_success() {
+    const IOSQE_CQE_SKIP_SUCCESS: u8 = 1 << 6;
+
+    let (mut sq, _, _) = urio::new(8).unwrap();
+    let mut packer = sq.alloc_sqe().unwrap();
+    packer.packup_write_owned(0, TEXT, 0);
+    packer.flags(IOSQE_CQE_SKIP_SUCCESS);
+}
+
+#[test]
+#[should_panic(expected = "IOSQE_CQE_SKIP_SUCCESS")]
+fn skip_success_owned() {
+    const IOSQE_CQE_SKIP_SUCCESS: u8 = 1 << 6;
+
+    let (mut sq, _, _) = urio::new(8).unwrap();
+    let mut packer = sq.alloc_sqe().unwrap();
+    packer.flags(IOSQE_CQE_SKIP_SUCCESS);
+    packer.packup_write_owned(0, TEXT, 0);
+}
@@ -0,0 +1,113 @@
+use std::{
+    error::Error,
+    ffi::CString,
+    fs::{self, File},
+    os::unix::{fs::MetadataExt, io::AsRawFd},
+};
+
+use tempfile::tempdir;
+use urio::op::{AtFlags, RenameFlags};
+
+#[test]
+fn mkdirat_unlinkat() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let dir = tempdir()?;
+    let dirfile = File::open(dir.path())?;
+
+    let mut packer = sq.alloc_sqe()?;
+    packer.packup_mkdirat(dirfile.as_raw_fd(), CString::new("sub")?, 0o700);
+    // The path is kept until the completion, in place of the `user_data`.
+    packer.user_data(1);
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    let cqe = cq.reap_cqe()?;
+    assert_eq!(cqe.user_data(), 1);
+    cqe.result()?;
+    let metadata = fs::metadata(dir.path().join("sub"))?;
+    assert!(metadata.is_dir());
+    assert_eq!(metadata.mode() & 0o777, 0o700);
+
+    // A directory can't be removed without `REMOVEDIR`.
+    sq.alloc_sqe()?
+        .packup_unlinkat(dirfile.as_raw_fd(), CString::new("sub")?, AtFlags::empty());
+    sq.submit_and_wait(1)?;
+    let err = cq.reap_cqe()?.result().unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EISDIR));
+
+    sq.alloc_sqe()?.packup_unlinkat(
+        dirfile.as_raw_fd(),
+        CString::new("sub")?,
+        AtFlags::REMOVEDIR,
+    );
+    sq.submit_and_wait(1)?;
+    cq.reap_cqe()?.result()?;
+    assert!(!dir.path().join("sub").exists());
+
+    Ok(())
+}
+
+#[test]
+fn renameat() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let dir = tempdir()?;
+    let dirfile = File::open(dir.path())?;
+    let dirfd = dirfile.as_raw_fd();
+    fs::write(dir.path().join("a"), b"a")?;
+    fs::write(dir.path().join("b"), b"b")?;
+    let path = |name: &str| CString::new(name).unwrap();
+
+    sq.alloc_sqe()?
+        .packup_renameat(dirfd, path("a"), dirfd, path("b"), RenameFlags::NOREPLACE);
+    sq.submit_and_wait(1)?;
+    let err = cq.reap_cqe()?.result().unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EEXIST));
+
+    sq.alloc_sqe()?
+        .packup_renameat(dirfd, path("a"), dirfd, path("b"), RenameFlags::EXCHANGE);
+    sq.submit_and_wait(1)?;
+    cq.reap_cqe()?.result()?;
+    assert_eq!(fs::read(dir.path().join("a"))?, b"b");
+    assert_eq!(fs::read(dir.path().join("b"))?, b"a");
+
+    sq.alloc_sqe()?
+        .packup_renameat(dirfd, path("a"), dirfd, path("c"), RenameFlags::empty());
+    sq.submit_and_wait(1)?;
+    cq.reap_cqe()?.result()?;
+    assert!(!dir.path().join("a").exists());
+    assert_eq!(fs::read(dir.path().join("c"))?, b"b");
+
+    Ok(())
+}
+
+#[test]
+fn symlinkat_linkat() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let dir = tempdir()?;
+    let dirfile = File::open(dir.path())?;
+    let dirfd = dirfile.as_raw_fd();
+    fs::write(dir.path().join("file"), b"data")?;
+    let path = |name: &str| CString::new(name).unwrap();
+
+    sq.alloc_sqe()?
+        .packup_symlinkat(path("file"), dirfd, path("sym"));
+    sq.submit_and_wait(1)?;
+    cq.reap_cqe()?.result()?;
+    assert_eq!(fs::read_link(dir.path().join("sym"))?.as_os_str(), "file");
+
+    // Link to the file the symbolic link refers to.
+    sq.alloc_sqe()?.packup_linkat(
+        dirfd,
+        path("sym"),
+        dirfd,
+        path("hard"),
+        AtFlags::SYMLINK_FOLLOW,
+    );
+    sq.submit_and_wait(1)?;
+    cq.reap_cqe()?.result()?;
+    let metadata = fs::symlink_metadata(dir.path().join("hard"))?;
+    assert!(metadata.is_file());
+    assert_eq!(metadata.nlink(), 2);
+    assert_eq!(metadata.ino(), fs::metadata(dir.path().join("file"))?.ino());
+
+    Ok(())
+}
@@ -0,0 +1,203 @@
+use std::{
+    error::Error,
+    io::Write,
+    os::unix::{
+        io::AsRawFd,
+        net::{UnixDatagram, UnixStream},
+    },
+};
+
+use urio::op::{BufferGroup, MsgFlags};
+
+const BGID: u16 = 7;
+
+#[test]
+fn read_select() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let mut group = BufferGroup::new(BGID, 4, 64);
+
+    sq.alloc_sqe()?.packup_provide_buffers(&mut group);
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    cq.reap_cqe()?.result()?;
+
+    let mut tmpfile = tempfile::tempfile()?;
+    tmpfile.write_all(b"Lorem ipsum dolor sit amet")?;
+    sq.alloc_sqe()?
+        .packup_read_select(tmpfile.as_raw_fd(), &group, 6);
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+
+    let cqe = cq.reap_cqe()?;
+    assert!(cqe.buffer_id().unwrap() < group.len());
+    assert_eq!(group.get(&cqe), Some(&b"ipsum dolor sit amet"[..]));
+
+    Ok(())
+}
+
+#[test]
+fn recv_select_recycle() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let (mut tx, rx) = UnixStream::pair()?;
+    let mut group = BufferGroup::new(BGID, 1, 16);
+
+    sq.alloc_sqe()?.packup_provide_buffers(&mut group);
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    cq.reap_cqe()?.result()?;
+
+    tx.write_all(b"ping")?;
+    sq.alloc_sqe()?
+        .packup_recv_select(rx.as_raw_fd(), &group, MsgFlags::empty());
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    let cqe = cq.reap_cqe()?;
+    let bid = cqe.buffer_id().unwrap();
+    // The buffer can't be accessed while it's provided.
+    assert_eq!(group.buffer(bid, 4), None);
+    assert_eq!(group.get(&cqe), Some(&b"ping"[..]));
+    assert_eq!(group.buffer(bid, 4), Some(&b"ping"[..]));
+
+    // The only buffer in the group is in use.
+    tx.write_all(b"pong")?;
+    sq.alloc_sqe()?
+        .packup_recv_select(rx.as_raw_fd(), &group, MsgFlags::empty());
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    let err = cq.reap_cqe()?.result().unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ENOBUFS));
+
+    sq.alloc_sqe()?.packup_provide_buffer(&mut group, bid);
+    sq.alloc_sqe()?
+        .packup_recv_select(rx.as_raw_fd(), &group, MsgFlags::empty());
+    let submitted = sq.submit_and_wait(2)?;
+    assert_eq!(submitted, 2);
+    let mut cqes = cq.reap_cqes(2)?;
+    cqes.next().unwrap().result()?;
+    let cqe = cqes.next().unwrap();
+    assert_eq!(cqe.buffer_id(), Some(bid));
+    assert_eq!(group.get(&cqe), Some(&b"pong"[..]));
+
+    Ok(())
+}
+
+#[test]
+fn get_from_another_group() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let mut group = BufferGroup::new(BGID, 1, 64);
+    let mut other = BufferGroup::new(BGID + 1, 1, 64);
+
+    sq.alloc_sqe()?.packup_provide_buffers(&mut group);
+    sq.alloc_sqe()?.packup_provide_buffers(&mut other);
+    sq.submit_and_wait(2)?;
+    cq.reap_cqes(2)?
+        .try_for_each(|cqe| cqe.result().map(drop))?;
+
+    let mut tmpfile = tempfile::tempfile()?;
+    tmpfile.write_all(b"Lorem ipsum")?;
+    sq.alloc_sqe()?
+        .packup_read_select(tmpfile.as_raw_fd(), &other, 0);
+    sq.submit_and_wait(1)?;
+
+    // The buffer with the same id is still provided from the group.
+    let cqe = cq.reap_cqe()?;
+    assert_eq!(cqe.buffer_id(), Some(0));
+    assert_eq!(group.get(&cqe), None);
+    assert_eq!(group.buffer(0, 0), None);
+    assert_eq!(other.get(&cqe), Some(&b"Lorem ipsum"[..]));
+
+    Ok(())
+}
+
+#[test]
+fn recv_select_truncated() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let (tx, rx) = UnixDatagram::pair()?;
+    let mut group = BufferGroup::new(BGID, 1, 4);
+
+    sq.alloc_sqe()?.packup_provide_buffers(&mut group);
+    sq.submit_and_wait(1)?;
+    cq.reap_cqe()?.result()?;
+
+    // The result is the real length of the datagram.
+    tx.send(b"ping pong")?;
+    sq.alloc_sqe()?
+        .packup_recv_select(rx.as_raw_fd(), &group, MsgFlags::TRUNC);
+    sq.submit_and_wait(1)?;
+    let cqe = cq.reap_cqe()?;
+    assert_eq!(cqe.result()?, 9);
+    assert_eq!(group.get(&cqe), Some(&b"ping"[..]));
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "buf_len must fit in u32")]
+fn buf_len_too_large() {
+    BufferGroup::new(BGID, 1, u32::MAX as usize + 1);
+}
+
+#[test]
+fn remove_buffers() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let mut group = BufferGroup::new(BGID, 4, 64);
+
+    sq.alloc_sqe()?.packup_provide_buffers(&mut group);
+    sq.alloc_sqe()?.packup_remove_buffers(&group, 8);
+    let submitted = sq.submit_and_wait(2)?;
+    assert_eq!(submitted, 2);
+
+    let mut cqes = cq.reap_cqes(2)?;
+    cqes.next().unwrap().result()?;
+    assert_eq!(cqes.next().unwrap().result()?, 4);
+    drop(cqes);
+
+    // No buffer is left in the group.
+    sq.alloc_sqe()?.packup_remove_buffers(&group, 1);
+    sq.submit_and_wait(1)?;
+    assert_eq!(cq.reap_cqe()?.result()?, 0);
+
+    Ok(())
+}
+
+#[test]
+fn read_select_linked() -> Result<(), Box<dyn Error>> {
+    const IOSQE_IO_LINK: u8 = 1 << 2;
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    // A short read breaks the link.
+    let mut group = BufferGroup::new(BGID, 1, 11);
+
+    let mut tmpfile = tempfile::tempfile()?;
+    tmpfile.write_all(b"Lorem ipsum")?;
+    sq.alloc_sqe()?.packup_provide_buffers(&mut group);
+    sq.submit_and_wait(1)?;
+    cq.reap_cqe()?.result()?;
+
+    // The link flag doesn't clear the buffer select flag.
+    let mut packer = sq.alloc_sqe()?;
+    packer.packup_read_select(tmpfile.as_raw_fd(), &group, 0);
+    packer.user_data(1).flags(IOSQE_IO_LINK);
+    sq.alloc_sqe()?.user_data(2).packup_nop();
+    let submitted = sq.submit_and_wait(2)?;
+    assert_eq!(submitted, 2);
+
+    let mut cqes = cq.reap_cqes(2)?;
+    let cqe = cqes.next().unwrap();
+    assert_eq!(cqe.user_data(), 1);
+    assert_eq!(group.get(&cqe), Some(&b"Lorem ipsum"[..]));
+    let cqe = cqes.next().unwrap();
+    assert_eq!(cqe.user_data(), 2);
+    cqe.result()?;
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "already provided")]
+fn provide_buffer_twice() {
+    let (mut sq, _, _) = urio::new(8).unwrap();
+    let mut group = BufferGroup::new(BGID, 4, 64);
+
+    sq.alloc_sqe().unwrap().packup_provide_buffers(&mut group);
+    sq.alloc_sqe().unwrap().packup_provide_buffer(&mut group, 1);
+}
@@ -25,12 +25,16 @@ fn read_vectored() -> Result<(), Box<dyn Error>> {
     let (mut sq, mut cq, _) = urio::new(8)?;
 
     let mut tmpfile = tempfile::tempfile()?;
-    tmpfile.write_all(&TEXT)?;
+    tmpfile.write_all(TEXT)?;
     tmpfile.flush()?;
 
     let mut buf = [0; 4096];
-    sq.alloc_sqe()?
-        .packup_read_vectored(tmpfile.as_raw_fd(), &mut [IoSliceMut::new(&mut buf)], 0);
+    let mut bufs = [IoSliceMut::new(&mut buf)];
+    // The `bufs` outlive the operation.
+    unsafe {
+        sq.alloc_sqe()?
+            .packup_read_vectored(tmpfile.as_raw_fd(), &mut bufs, 0)
+    };
 
     let submitted = sq.submit_and_wait(1)?;
     assert_eq!(submitted, 1);
@@ -49,8 +53,11 @@ fn write_vectored() -> Result<(), Box<dyn Error>> {
 
     let mut tmpfile = tempfile::tempfile()?;
 
-    sq.alloc_sqe()?
-        .packup_write_vectored(tmpfile.as_raw_fd(), &[IoSlice::new(TEXT)], 0);
+    let bufs = [IoSlice::new(TEXT)];
+    unsafe {
+        sq.alloc_sqe()?
+            .packup_write_vectored(tmpfile.as_raw_fd(), &bufs, 0)
+    };
 
     let submitted = sq.submit_and_wait(1)?;
     assert_eq!(submitted, 1);
@@ -66,17 +73,63 @@ fn write_vectored() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[test]
+fn readv_owned() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+
+    let mut tmpfile = tempfile::tempfile()?;
+    tmpfile.write_all(TEXT)?;
+    tmpfile.flush()?;
+
+    // The read data is spread over the buffers in order.
+    let bufs = vec![Vec::with_capacity(16), Vec::with_capacity(4096)];
+    sq.alloc_sqe()?
+        .packup_readv_owned(tmpfile.as_raw_fd(), bufs, 0);
+    sq.submit_and_wait(1)?;
+
+    let mut cqe = cq.reap_cqe()?;
+    assert_eq!(cqe.result()? as usize, TEXT.len());
+    let bufs = cqe.take_buf::<Vec<Vec<u8>>>().unwrap();
+    assert_eq!(bufs[0], &TEXT[..16]);
+    assert_eq!(bufs[1], &TEXT[16..]);
+
+    Ok(())
+}
+
+#[test]
+fn writev_owned() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+
+    let mut tmpfile = tempfile::tempfile()?;
+    let bufs = vec![&TEXT[..16], &TEXT[16..]];
+    sq.alloc_sqe()?
+        .packup_writev_owned(tmpfile.as_raw_fd(), bufs, 0);
+    sq.submit_and_wait(1)?;
+
+    let mut cqe = cq.reap_cqe()?;
+    assert_eq!(cqe.result()? as usize, TEXT.len());
+    assert_eq!(cqe.take_buf::<Vec<&[u8]>>().unwrap().len(), 2);
+
+    let mut buf = vec![];
+    tmpfile.read_to_end(&mut buf)?;
+    assert_eq!(buf, TEXT);
+
+    Ok(())
+}
+
 #[test]
 fn read_fixed() -> Result<(), Box<dyn Error>> {
     let (mut sq, mut cq, rgstr) = urio::new(8)?;
 
     let mut tmpfile = tempfile::tempfile()?;
-    tmpfile.write_all(&TEXT)?;
+    tmpfile.write_all(TEXT)?;
     tmpfile.flush()?;
     let mut buf = [0; 4096];
     rgstr.register_buffers(&[IoSlice::new(&buf)])?;
-    sq.alloc_sqe()?
-        .packup_read_fixed(tmpfile.as_raw_fd(), &mut buf, 0, 0);
+    unsafe {
+        sq.alloc_sqe()?
+            .packup_read_fixed(tmpfile.as_raw_fd(), &mut buf, 0, 0)
+    };
 
     let submitted = sq.submit_and_wait(1)?;
     assert_eq!(submitted, 1);
@@ -95,8 +148,10 @@ fn write_fixed() -> Result<(), Box<dyn Error>> {
     let mut tmpfile = tempfile::tempfile()?;
     let mut buf = Vec::from(TEXT);
     rgstr.register_buffers(&[IoSlice::new(&buf)])?;
-    sq.alloc_sqe()?
-        .packup_write_fixed(tmpfile.as_raw_fd(), &buf, 0, 0);
+    unsafe {
+        sq.alloc_sqe()?
+            .packup_write_fixed(tmpfile.as_raw_fd(), &buf, 0, 0)
+    };
 
     let submitted = sq.submit_and_wait(1)?;
     assert_eq!(submitted, 1);
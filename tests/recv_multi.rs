@@ -0,0 +1,110 @@
+use std::{
+    error::Error,
+    mem,
+    net::UdpSocket,
+    os::unix::{io::AsRawFd, net::UnixDatagram},
+};
+
+use urio::{
+    op::{MsgFlags, MsgHdr, RecvMsgOut},
+    BufRingFlags,
+};
+
+const BGID: u16 = 5;
+const MSGS: [&[u8]; 3] = [b"Lorem ipsum", b"dolor sit amet", b"consectetur"];
+
+#[test]
+fn recv_multi_datagram() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, rgstr) = urio::new(8)?;
+    let (tx, rx) = UnixDatagram::pair()?;
+    let mut buf_ring = rgstr.register_buf_ring(BGID, 4, 64, BufRingFlags::empty())?;
+
+    sq.alloc_sqe()?
+        .packup_recv_multi(rx.as_raw_fd(), &buf_ring, MsgFlags::empty());
+    let submitted = sq.submit()?;
+    assert_eq!(submitted, 1);
+
+    for msg in MSGS.iter() {
+        tx.send(msg)?;
+        sq.submit_and_wait(1)?;
+        let cqe = cq.reap_cqe()?;
+        assert!(cqe.has_more());
+        let bid = cqe.buffer_id().unwrap();
+        assert_eq!(buf_ring.get(&cqe), Some(*msg));
+        buf_ring.recycle(bid);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn recv_multi_out_of_buffers() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, rgstr) = urio::new(8)?;
+    let (tx, rx) = UnixDatagram::pair()?;
+    let mut buf_ring = rgstr.register_buf_ring(BGID, 2, 64, BufRingFlags::empty())?;
+    for msg in MSGS.iter() {
+        tx.send(msg)?;
+    }
+
+    sq.alloc_sqe()?
+        .packup_recv_multi(rx.as_raw_fd(), &buf_ring, MsgFlags::empty());
+    let submitted = sq.submit_and_wait(3)?;
+    assert_eq!(submitted, 1);
+
+    let mut cqes = cq.reap_cqes(3)?;
+    for msg in &MSGS[..2] {
+        let cqe = cqes.next().unwrap();
+        assert!(cqe.has_more());
+        assert_eq!(buf_ring.get(&cqe), Some(*msg));
+    }
+    // The operation is terminated since no buffer is left.
+    let cqe = cqes.next().unwrap();
+    assert!(!cqe.has_more());
+    assert_eq!(
+        cqe.result().unwrap_err().raw_os_error(),
+        Some(libc::ENOBUFS)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn recvmsg_multi_udp() -> Result<(), Box<dyn Error>> {
+    // The `msg` outlives the io_uring instance, which is still receiving.
+    let msg = MsgHdr::with_reserved(mem::size_of::<libc::sockaddr_storage>() as _, 0);
+    let (mut sq, mut cq, rgstr) = urio::new(8)?;
+    let tx = UdpSocket::bind("127.0.0.1:0")?;
+    let rx = UdpSocket::bind("127.0.0.1:0")?;
+    let mut buf_ring = rgstr.register_buf_ring(BGID, 4, 256, BufRingFlags::empty())?;
+
+    unsafe {
+        sq.alloc_sqe()?
+            .packup_recvmsg_multi(rx.as_raw_fd(), &msg, &buf_ring, MsgFlags::empty())
+    };
+    let submitted = sq.submit()?;
+    assert_eq!(submitted, 1);
+
+    for payload in MSGS.iter() {
+        tx.send_to(payload, rx.local_addr()?)?;
+        sq.submit_and_wait(1)?;
+        let cqe = cq.reap_cqe()?;
+        assert!(cqe.has_more());
+
+        let bid = cqe.buffer_id().unwrap();
+        let out = RecvMsgOut::parse(buf_ring.get(&cqe).unwrap(), &msg).unwrap();
+        assert_eq!(out.payload(), *payload);
+        assert_eq!(out.payload_len(), payload.len());
+        assert!(out.control().is_empty());
+        assert!(out.msg_flags().is_empty());
+        assert_eq!(out.name().as_socket_addr(), Some(tx.local_addr()?));
+        buf_ring.recycle(bid);
+    }
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "larger than any socket address")]
+fn recvmsg_multi_name_too_long() {
+    MsgHdr::with_reserved(mem::size_of::<libc::sockaddr_storage>() as u32 + 1, 0);
+}
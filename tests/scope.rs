@@ -0,0 +1,102 @@
+use std::{
+    error::Error,
+    io::Write,
+    os::unix::{io::AsRawFd, net::UnixStream},
+    panic::{self, AssertUnwindSafe},
+    thread,
+    time::Duration,
+};
+
+use urio::op::MsgFlags;
+
+const TEXT: &[u8] = b"Hello, io_uring!";
+
+#[test]
+fn scope_write_read() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let tmpfile = tempfile::tempfile()?;
+    let fd = tmpfile.as_raw_fd();
+
+    let mut buf = [0; 64];
+    let len = sq.scope(&mut cq, |s| -> Result<_, Box<dyn Error>> {
+        s.write(fd, TEXT, 0)?.user_data(1);
+        s.submit_and_wait(1)?;
+        let cqe = s.reap_cqe()?;
+        assert_eq!(cqe.user_data(), 1);
+        assert_eq!(cqe.result()? as usize, TEXT.len());
+
+        s.read(fd, &mut buf, 0)?.user_data(2);
+        s.submit_and_wait(1)?;
+        let cqe = s.reap_cqe()?;
+        assert_eq!(cqe.user_data(), 2);
+        Ok(cqe.result()? as usize)
+    })?;
+    assert_eq!(&buf[..len], TEXT);
+
+    Ok(())
+}
+
+#[test]
+fn scope_waits_for_operations() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let (mut tx, rx) = UnixStream::pair()?;
+
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        tx.write_all(TEXT)
+    });
+
+    let mut buf = [0; 64];
+    sq.scope(&mut cq, |s| -> Result<_, Box<dyn Error>> {
+        s.recv(rx.as_raw_fd(), &mut buf, MsgFlags::empty())?
+            .user_data(1);
+        s.submit()?;
+        // It returns without waiting for the data.
+        Ok(())
+    })?;
+    handle.join().unwrap()?;
+
+    // The event is kept in the CQ.
+    assert_eq!(cq.len(), 1);
+    let cqe = cq.reap_cqe()?;
+    assert_eq!(cqe.user_data(), 1);
+    let len = cqe.result()? as usize;
+    assert_eq!(&buf[..len], TEXT);
+
+    Ok(())
+}
+
+#[test]
+fn scope_cancels_on_panic() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let (_tx, rx) = UnixStream::pair()?;
+
+    let mut buf = [0; 64];
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        sq.scope(&mut cq, |s| {
+            s.recv(rx.as_raw_fd(), &mut buf, MsgFlags::empty())
+                .unwrap()
+                .user_data(1);
+            s.submit().unwrap();
+            panic!("oops");
+        })
+    }));
+    assert!(result.is_err());
+
+    // Only the event of the canceled operation is left.
+    assert_eq!(cq.len(), 1);
+    let cqe = cq.reap_cqe()?;
+    assert_eq!(cqe.user_data(), 1);
+    let err = cqe.result().unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ECANCELED));
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "another io_uring instance")]
+fn scope_with_another_cq() {
+    let (mut sq, _, _) = urio::new(8).unwrap();
+    let (_, mut cq, _) = urio::new(8).unwrap();
+    sq.scope(&mut cq, |_| {});
+}
@@ -0,0 +1,97 @@
+use std::{
+    error::Error,
+    io::{IoSlice, IoSliceMut},
+    net::UdpSocket,
+    os::unix::{
+        io::AsRawFd,
+        net::{UnixDatagram, UnixStream},
+    },
+};
+
+use urio::op::{MsgFlags, MsgHdr, SockAddrStorage};
+
+#[test]
+fn send_recv_stream() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let (tx, rx) = UnixStream::pair()?;
+
+    sq.alloc_sqe()?
+        .packup_send_owned(tx.as_raw_fd(), &b"ping"[..], MsgFlags::NOSIGNAL);
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    assert_eq!(cq.reap_cqe()?.result()?, 4);
+
+    sq.alloc_sqe()?
+        .packup_recv_owned(rx.as_raw_fd(), Vec::with_capacity(16), MsgFlags::empty());
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    let mut cqe = cq.reap_cqe()?;
+    assert_eq!(cqe.result()?, 4);
+    assert_eq!(cqe.take_buf::<Vec<u8>>().unwrap(), b"ping");
+
+    Ok(())
+}
+
+#[test]
+fn sendmsg_recvmsg_udp() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let tx = UdpSocket::bind("127.0.0.1:0")?;
+    let rx = UdpSocket::bind("127.0.0.1:0")?;
+
+    let dst = SockAddrStorage::from(rx.local_addr()?);
+    let bufs = [IoSlice::new(b"hello, "), IoSlice::new(b"urio")];
+    let mut msg = MsgHdr::new();
+    msg.name(&dst).iovecs(&bufs);
+    // The `msg` outlives the operation.
+    unsafe {
+        sq.alloc_sqe()?
+            .packup_sendmsg(tx.as_raw_fd(), &msg, MsgFlags::empty())
+    };
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    assert_eq!(cq.reap_cqe()?.result()?, 11);
+
+    let mut src = SockAddrStorage::new();
+    let (mut head, mut tail) = ([0; 5], [0; 16]);
+    let mut bufs = [IoSliceMut::new(&mut head), IoSliceMut::new(&mut tail)];
+    let mut msg = MsgHdr::new();
+    msg.name_mut(&mut src).iovecs_mut(&mut bufs);
+    unsafe {
+        sq.alloc_sqe()?
+            .packup_recvmsg(rx.as_raw_fd(), &mut msg, MsgFlags::empty())
+    };
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    let len = cq.reap_cqe()?.result()? as usize;
+    assert_eq!(len, 11);
+    assert!(msg.msg_flags().is_empty());
+
+    assert_eq!(&head, b"hello");
+    assert_eq!(&tail[..len - head.len()], b", urio");
+    assert_eq!(src.as_socket_addr(), Some(tx.local_addr()?));
+
+    Ok(())
+}
+
+#[test]
+fn recvmsg_truncated_datagram() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let (tx, rx) = UnixDatagram::pair()?;
+    tx.send(b"Lorem ipsum dolor sit amet")?;
+
+    let mut buf = [0; 5];
+    let mut bufs = [IoSliceMut::new(&mut buf)];
+    let mut msg = MsgHdr::new();
+    msg.iovecs_mut(&mut bufs);
+    unsafe {
+        sq.alloc_sqe()?
+            .packup_recvmsg(rx.as_raw_fd(), &mut msg, MsgFlags::empty())
+    };
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    assert_eq!(cq.reap_cqe()?.result()?, 5);
+    assert!(msg.msg_flags().contains(MsgFlags::TRUNC));
+    assert_eq!(&buf, b"Lorem");
+
+    Ok(())
+}
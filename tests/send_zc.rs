@@ -0,0 +1,140 @@
+mod common;
+
+use std::{
+    error::Error,
+    io::{IoSlice, Read},
+    os::unix::io::AsRawFd,
+};
+
+use urio::{
+    op::{MsgFlags, MsgHdr},
+    Cq,
+};
+
+use common::tcp_pair;
+
+const TEXT: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit.";
+
+/// Reap the result and the notification of a zero-copy send, returning the
+/// number of bytes sent.
+fn reap_zc(cq: &mut Cq) -> Result<u32, Box<dyn Error>> {
+    let cqe = cq.reap_cqe()?;
+    assert_eq!(cqe.user_data(), 42);
+    assert!(!cqe.is_notification());
+    assert!(cqe.has_more());
+    let len = cqe.result()?;
+
+    let notif = cq.reap_cqe()?;
+    assert_eq!(notif.user_data(), 42);
+    assert!(notif.is_notification());
+    assert!(!notif.has_more());
+
+    Ok(len)
+}
+
+#[test]
+fn send_zc_owned() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let (tx, mut rx) = tcp_pair()?;
+
+    sq.alloc_sqe()?.user_data(42).packup_send_zc_owned(
+        tx.as_raw_fd(),
+        Vec::from(TEXT),
+        MsgFlags::empty(),
+    );
+    let submitted = sq.submit_and_wait(2)?;
+    assert_eq!(submitted, 1);
+
+    // The buffer is still used by the kernel until the notification.
+    let mut cqe = cq.reap_cqe()?;
+    assert_eq!(cqe.user_data(), 42);
+    assert!(cqe.has_more());
+    assert_eq!(cqe.result()?, TEXT.len() as _);
+    assert!(cqe.take_buf::<Vec<u8>>().is_none());
+
+    let mut notif = cq.reap_cqe()?;
+    assert_eq!(notif.user_data(), 42);
+    assert!(notif.is_notification());
+    assert!(!notif.has_more());
+    assert_eq!(notif.take_buf::<Vec<u8>>().unwrap(), TEXT);
+
+    let mut buf = [0; TEXT.len()];
+    rx.read_exact(&mut buf)?;
+    assert_eq!(&buf[..], TEXT);
+
+    Ok(())
+}
+
+#[test]
+fn send_zc() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let (tx, mut rx) = tcp_pair()?;
+
+    // The `TEXT` is static, so it outlives the operation.
+    unsafe {
+        sq.alloc_sqe()?
+            .user_data(42)
+            .packup_send_zc(tx.as_raw_fd(), TEXT, MsgFlags::empty())
+    };
+    let submitted = sq.submit_and_wait(2)?;
+    assert_eq!(submitted, 1);
+    assert_eq!(reap_zc(&mut cq)?, TEXT.len() as _);
+
+    let mut buf = [0; TEXT.len()];
+    rx.read_exact(&mut buf)?;
+    assert_eq!(&buf[..], TEXT);
+
+    Ok(())
+}
+
+#[test]
+fn send_zc_fixed() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, rgstr) = urio::new(8)?;
+    let (tx, mut rx) = tcp_pair()?;
+
+    let buf = Vec::from(TEXT);
+    rgstr.register_buffers(&[IoSlice::new(&buf)])?;
+    // The `buf` outlives the operation.
+    unsafe {
+        sq.alloc_sqe()?.user_data(42).packup_send_zc_fixed(
+            tx.as_raw_fd(),
+            &buf[6..],
+            MsgFlags::empty(),
+            0,
+        )
+    };
+    let submitted = sq.submit_and_wait(2)?;
+    assert_eq!(submitted, 1);
+    assert_eq!(reap_zc(&mut cq)?, TEXT.len() as u32 - 6);
+
+    let mut buf = [0; TEXT.len() - 6];
+    rx.read_exact(&mut buf)?;
+    assert_eq!(&buf[..], &TEXT[6..]);
+
+    Ok(())
+}
+
+#[test]
+fn sendmsg_zc() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let (tx, mut rx) = tcp_pair()?;
+
+    let bufs = [IoSlice::new(&TEXT[..6]), IoSlice::new(&TEXT[6..])];
+    let mut msg = MsgHdr::new();
+    msg.iovecs(&bufs);
+    // The `msg` outlives the operation.
+    unsafe {
+        sq.alloc_sqe()?
+            .user_data(42)
+            .packup_sendmsg_zc(tx.as_raw_fd(), &msg, MsgFlags::empty())
+    };
+    let submitted = sq.submit_and_wait(2)?;
+    assert_eq!(submitted, 1);
+    assert_eq!(reap_zc(&mut cq)?, TEXT.len() as _);
+
+    let mut buf = [0; TEXT.len()];
+    rx.read_exact(&mut buf)?;
+    assert_eq!(&buf[..], TEXT);
+
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use std::{
+    error::Error,
+    io::{Read, Write},
+    net::{Shutdown, TcpListener, TcpStream},
+    os::unix::{
+        io::{AsRawFd, FromRawFd},
+        net::UnixStream,
+    },
+};
+
+use urio::op::{Domain, Protocol, SockAddrStorage, SockFlags, SockType};
+
+#[test]
+fn socket_bind_listen() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+
+    sq.alloc_sqe()?.packup_socket(
+        Domain::INET,
+        SockType::STREAM,
+        Protocol::TCP,
+        SockFlags::CLOEXEC,
+    );
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    let listener = unsafe { TcpListener::from_raw_fd(cq.reap_cqe()?.result()? as _) };
+
+    let addr = SockAddrStorage::from("127.0.0.1:0".parse::<std::net::SocketAddr>()?);
+    sq.alloc_sqe()?.packup_bind(listener.as_raw_fd(), addr);
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    cq.reap_cqe()?.result()?;
+
+    sq.alloc_sqe()?.packup_listen(listener.as_raw_fd(), 8);
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    cq.reap_cqe()?.result()?;
+
+    let mut client = TcpStream::connect(listener.local_addr()?)?;
+    let (mut server, _) = listener.accept()?;
+    client.write_all(b"ping")?;
+    let mut buf = [0; 4];
+    server.read_exact(&mut buf)?;
+    assert_eq!(&buf, b"ping");
+
+    Ok(())
+}
+
+#[test]
+fn socket_direct() -> Result<(), Box<dyn Error>> {
+    const IOSQE_FIXED_FILE: u8 = 1 << 0;
+    let (mut sq, mut cq, rgstr) = urio::new(8)?;
+    rgstr.register_files_sparse(4)?;
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+
+    sq.alloc_sqe()?.user_data(1).packup_socket_direct(
+        Domain::INET,
+        SockType::DGRAM,
+        Protocol::DEFAULT,
+        SockFlags::empty(),
+        None,
+    );
+    // `SockFlags::CLOEXEC` is stripped for the fixed file table.
+    sq.alloc_sqe()?.user_data(2).packup_socket_direct(
+        Domain::INET,
+        SockType::STREAM,
+        Protocol::TCP,
+        SockFlags::CLOEXEC,
+        Some(3),
+    );
+    let submitted = sq.submit_and_wait(2)?;
+    assert_eq!(submitted, 2);
+
+    for cqe in cq.reap_cqes(2)? {
+        match cqe.user_data() {
+            // The first free slot is allocated.
+            1 => assert_eq!(cqe.result()?, 0),
+            // The result is zero if the slot is given.
+            2 => assert_eq!(cqe.result()?, 0),
+            _ => unreachable!(),
+        }
+    }
+
+    // The socket in the slot is connected to the listener.
+    let addr = SockAddrStorage::from(listener.local_addr()?);
+    sq.alloc_sqe()?
+        .flags(IOSQE_FIXED_FILE)
+        .packup_connect(3, addr);
+    sq.submit_and_wait(1)?;
+    cq.reap_cqe()?.result()?;
+    let (_, peer) = listener.accept()?;
+    assert!(peer.ip().is_loopback());
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "out of range")]
+fn socket_direct_out_of_range() {
+    let (mut sq, _, _) = urio::new(8).unwrap();
+    sq.alloc_sqe().unwrap().packup_socket_direct(
+        Domain::INET,
+        SockType::DGRAM,
+        Protocol::DEFAULT,
+        SockFlags::empty(),
+        // It would collide with `IORING_FILE_INDEX_ALLOC`.
+        Some(u32::MAX - 1),
+    );
+}
+
+#[test]
+fn shutdown_write() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let (tx, mut rx) = UnixStream::pair()?;
+
+    sq.alloc_sqe()?
+        .packup_shutdown(tx.as_raw_fd(), Shutdown::Write);
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    cq.reap_cqe()?.result()?;
+
+    let mut buf = Vec::new();
+    assert_eq!(rx.read_to_end(&mut buf)?, 0);
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+mod common;
+
+use std::{
+    error::Error,
+    io::{Read, Write},
+    os::unix::io::AsRawFd,
+};
+
+use tempfile::tempfile;
+use urio::op::{Fd, SpliceFlags};
+
+use common::pipe;
+
+const TEXT: &[u8] = b"Hello, io_uring!";
+
+#[test]
+fn splice_file_to_pipe() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let mut file = tempfile()?;
+    file.write_all(TEXT)?;
+    let (mut rx, tx) = pipe()?;
+
+    // Skip the first 7 bytes of the file.
+    sq.alloc_sqe()?.packup_splice(
+        file.as_raw_fd(),
+        Some(7),
+        tx.as_raw_fd(),
+        None,
+        TEXT.len() as _,
+        SpliceFlags::empty(),
+    );
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    assert_eq!(cq.reap_cqe()?.result()?, TEXT.len() as u32 - 7);
+
+    drop(tx);
+    let mut buf = Vec::new();
+    rx.read_to_end(&mut buf)?;
+    assert_eq!(buf, &TEXT[7..]);
+
+    Ok(())
+}
+
+#[test]
+fn splice_fixed_pipe_to_file() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, rgstr) = urio::new(8)?;
+    let (rx, mut tx) = pipe()?;
+    let mut file = tempfile()?;
+    rgstr.register_files(&[rx.as_raw_fd()])?;
+    tx.write_all(TEXT)?;
+
+    sq.alloc_sqe()?.packup_splice(
+        Fd::Fixed(0),
+        None,
+        file.as_raw_fd(),
+        Some(0),
+        TEXT.len() as _,
+        SpliceFlags::MOVE,
+    );
+    sq.submit_and_wait(1)?;
+    assert_eq!(cq.reap_cqe()?.result()?, TEXT.len() as u32);
+
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf)?;
+    assert_eq!(buf, TEXT);
+
+    Ok(())
+}
+
+#[test]
+fn tee() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, rgstr) = urio::new(8)?;
+    let (mut rx1, mut tx1) = pipe()?;
+    let (mut rx2, tx2) = pipe()?;
+    rgstr.register_files(&[rx1.as_raw_fd()])?;
+    tx1.write_all(TEXT)?;
+    drop(tx1);
+
+    sq.alloc_sqe()?.packup_tee(
+        Fd::Fixed(0),
+        tx2.as_raw_fd(),
+        TEXT.len() as _,
+        SpliceFlags::empty(),
+    );
+    sq.submit_and_wait(1)?;
+    assert_eq!(cq.reap_cqe()?.result()?, TEXT.len() as u32);
+    drop(tx2);
+
+    // The data is duplicated without being consumed from the first pipe.
+    let mut buf = Vec::new();
+    rx2.read_to_end(&mut buf)?;
+    assert_eq!(buf, TEXT);
+    let mut buf = Vec::new();
+    rx1.read_to_end(&mut buf)?;
+    assert_eq!(buf, TEXT);
+
+    Ok(())
+}
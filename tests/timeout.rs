@@ -0,0 +1,83 @@
+use std::{
+    error::Error,
+    thread,
+    time::{Duration, Instant},
+};
+
+use urio::op::{TickCounter, Timespec};
+
+const INTERVAL: Duration = Duration::from_millis(10);
+
+#[test]
+fn periodic_timeout() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let interval = Timespec::from(INTERVAL);
+
+    let start = Instant::now();
+    let mut counter = TickCounter::new(INTERVAL);
+    sq.alloc_sqe()?.packup_periodic_timeout(&interval, 3);
+    sq.submit()?;
+
+    loop {
+        sq.submit_and_wait(1)?;
+        let cqe = cq.reap_cqe()?;
+        let err = cqe.result().unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::ETIME));
+        assert!(counter.tick(&cqe).is_some());
+        if !cqe.has_more() {
+            break;
+        }
+    }
+    assert_eq!(counter.ticks(), 3);
+    assert!(start.elapsed() >= 3 * INTERVAL);
+
+    // It's terminated after the last tick.
+    thread::sleep(2 * INTERVAL);
+    assert!(cq.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn periodic_timeout_missed_ticks() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let interval = Timespec::from(INTERVAL);
+
+    let mut counter = TickCounter::new(INTERVAL);
+    sq.alloc_sqe()?.packup_periodic_timeout(&interval, 0);
+    sq.submit()?;
+
+    for _ in 0..2 {
+        sq.submit_and_wait(1)?;
+        let cqe = cq.reap_cqe()?;
+        assert!(cqe.has_more());
+        counter.tick(&cqe);
+    }
+
+    // Lag behind, so that the ticks are piled up in the CQ.
+    thread::sleep(6 * INTERVAL);
+    let missed = counter.tick(&cq.reap_cqe()?).unwrap();
+    assert!(missed >= 3);
+    assert_eq!(counter.missed(), missed);
+
+    // Catch up with the pending ticks.
+    let pending = cq.len();
+    for cqe in cq.reap_cqes(pending)? {
+        counter.tick(&cqe);
+    }
+    assert_eq!(counter.ticks(), 3 + pending as u64);
+    assert!(counter.missed() < missed);
+
+    // An event which is not a tick is not counted.
+    sq.alloc_sqe()?.user_data(1).packup_nop();
+    sq.submit_and_wait(1)?;
+    loop {
+        let cqe = cq.reap_cqe()?;
+        if cqe.user_data() == 1 {
+            assert_eq!(counter.tick(&cqe), None);
+            break;
+        }
+    }
+
+    Ok(())
+}
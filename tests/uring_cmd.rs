@@ -0,0 +1,100 @@
+mod common;
+
+use std::{error::Error, io::Write, os::unix::io::AsRawFd};
+
+use common::tcp_pair;
+
+#[test]
+fn siocinq_siocoutq() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let (mut client, server) = tcp_pair()?;
+
+    client.write_all(b"Hello")?;
+    // Wait until the data arrives.
+    server.peek(&mut [0; 5])?;
+
+    sq.alloc_sqe()?
+        .user_data(1)
+        .packup_siocinq(server.as_raw_fd());
+    sq.alloc_sqe()?
+        .user_data(2)
+        .packup_siocoutq(client.as_raw_fd());
+    let submitted = sq.submit_and_wait(2)?;
+    assert_eq!(submitted, 2);
+    for cqe in cq.reap_cqes(2)? {
+        match cqe.user_data() {
+            1 => assert_eq!(cqe.result()?, 5),
+            // Some of them may not be acknowledged yet.
+            2 => assert!(cqe.result()? <= 5),
+            _ => unreachable!(),
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn getsockopt_setsockopt() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let (client, _server) = tcp_pair()?;
+    let fd = client.as_raw_fd();
+
+    sq.alloc_sqe()?.packup_getsockopt(
+        fd,
+        libc::SOL_SOCKET,
+        libc::SO_KEEPALIVE,
+        Vec::with_capacity(4),
+    );
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    let mut cqe = cq.reap_cqe()?;
+    // The result is the size of the value.
+    assert_eq!(cqe.result()?, 4);
+    let optval = cqe.take_buf::<Vec<u8>>().unwrap();
+    assert_eq!(optval, 0i32.to_ne_bytes());
+
+    sq.alloc_sqe()?.packup_setsockopt(
+        fd,
+        libc::SOL_SOCKET,
+        libc::SO_KEEPALIVE,
+        1i32.to_ne_bytes().to_vec(),
+    );
+    sq.submit_and_wait(1)?;
+    assert_eq!(cq.reap_cqe()?.result()?, 0);
+
+    // The buffer is filled from the start again.
+    sq.alloc_sqe()?
+        .packup_getsockopt(fd, libc::SOL_SOCKET, libc::SO_KEEPALIVE, optval);
+    sq.submit_and_wait(1)?;
+    let mut cqe = cq.reap_cqe()?;
+    cqe.result()?;
+    assert_eq!(cqe.take_buf::<Vec<u8>>().unwrap(), 1i32.to_ne_bytes());
+
+    // The other levels are supported for setting.
+    sq.alloc_sqe()?.packup_setsockopt(
+        fd,
+        libc::IPPROTO_TCP,
+        libc::TCP_NODELAY,
+        1i32.to_ne_bytes().to_vec(),
+    );
+    sq.submit_and_wait(1)?;
+    cq.reap_cqe()?.result()?;
+    assert!(client.nodelay()?);
+
+    Ok(())
+}
+
+#[test]
+fn uring_cmd_unsupported() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let file = tempfile::tempfile()?;
+
+    // A regular file on the most filesystems doesn't support the commands.
+    sq.alloc_sqe()?
+        .packup_uring_cmd(file.as_raw_fd(), 0, &[0; 16]);
+    sq.submit_and_wait(1)?;
+    let err = cq.reap_cqe()?.result().unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EOPNOTSUPP));
+
+    Ok(())
+}
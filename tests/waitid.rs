@@ -0,0 +1,55 @@
+use std::{error::Error, process::Command};
+
+use urio::op::{ChildStatus, WaitId, WaitInfo, WaitOptions};
+
+#[test]
+fn waitid_exited() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let child = Command::new("sh").args(["-c", "exit 3"]).spawn()?;
+
+    let info = Box::new(WaitInfo::new());
+    sq.alloc_sqe()?
+        .packup_waitid(WaitId::Pid(child.id() as _), WaitOptions::EXITED, info);
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    let mut cqe = cq.reap_cqe()?;
+    assert_eq!(cqe.result()?, 0);
+    let info = cqe.take_buf::<Box<WaitInfo>>().unwrap();
+
+    assert_eq!(info.pid(), child.id() as _);
+    assert_eq!(info.uid(), unsafe { libc::getuid() });
+    assert_eq!(info.status(), Some(ChildStatus::Exited(3)));
+
+    Ok(())
+}
+
+#[test]
+fn waitid_killed() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let mut child = Command::new("sleep").arg("10").spawn()?;
+
+    // Nothing has happened yet.
+    sq.alloc_sqe()?.packup_waitid(
+        WaitId::Pid(child.id() as _),
+        WaitOptions::EXITED | WaitOptions::NOHANG,
+        Box::default(),
+    );
+    sq.submit_and_wait(1)?;
+    let mut cqe = cq.reap_cqe()?;
+    assert_eq!(cqe.result()?, 0);
+    let info = cqe.take_buf::<Box<WaitInfo>>().unwrap();
+    assert_eq!(info.pid(), 0);
+    assert_eq!(info.status(), None);
+
+    sq.alloc_sqe()?
+        .packup_waitid(WaitId::Pid(child.id() as _), WaitOptions::EXITED, info);
+    child.kill()?;
+    sq.submit_and_wait(1)?;
+    let mut cqe = cq.reap_cqe()?;
+    assert_eq!(cqe.result()?, 0);
+    let info = cqe.take_buf::<Box<WaitInfo>>().unwrap();
+    assert_eq!(info.pid(), child.id() as _);
+    assert_eq!(info.status(), Some(ChildStatus::Killed(libc::SIGKILL)));
+
+    Ok(())
+}
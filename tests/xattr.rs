@@ -0,0 +1,101 @@
+use std::{
+    error::Error,
+    ffi::CString,
+    os::unix::{ffi::OsStrExt, io::AsRawFd},
+};
+
+use tempfile::NamedTempFile;
+use urio::op::XattrFlags;
+
+const VALUE: &[u8] = b"application/octet-stream";
+
+/// Create a temporary file on tmpfs, which supports user extended attributes.
+fn tmpfs_file() -> std::io::Result<NamedTempFile> {
+    NamedTempFile::new_in("/dev/shm")
+}
+
+#[test]
+fn fsetxattr_fgetxattr() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let file = tmpfs_file()?;
+    let fd = file.as_file().as_raw_fd();
+    let name = || CString::new("user.mime_type").unwrap();
+
+    sq.alloc_sqe()?
+        .packup_fsetxattr(fd, name(), VALUE, XattrFlags::CREATE);
+    let submitted = sq.submit_and_wait(1)?;
+    assert_eq!(submitted, 1);
+    let mut cqe = cq.reap_cqe()?;
+    cqe.result()?;
+    assert_eq!(cqe.take_buf::<&[u8]>(), Some(VALUE));
+
+    // Query the size of the value first.
+    sq.alloc_sqe()?.packup_fgetxattr(fd, name(), Vec::new());
+    sq.submit_and_wait(1)?;
+    let mut cqe = cq.reap_cqe()?;
+    let size = cqe.result()? as usize;
+    assert_eq!(size, VALUE.len());
+    // Nothing is filled, though the result is beyond the buffer.
+    assert_eq!(cqe.take_buf::<Vec<u8>>().unwrap(), b"");
+
+    sq.alloc_sqe()?
+        .packup_fgetxattr(fd, name(), Vec::with_capacity(size));
+    sq.submit_and_wait(1)?;
+    let mut cqe = cq.reap_cqe()?;
+    assert_eq!(cqe.result()? as usize, size);
+    assert_eq!(cqe.take_buf::<Vec<u8>>().unwrap(), VALUE);
+
+    // It already exists.
+    sq.alloc_sqe()?
+        .packup_fsetxattr(fd, name(), &b"text/plain"[..], XattrFlags::CREATE);
+    sq.submit_and_wait(1)?;
+    let err = cq.reap_cqe()?.result().unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EEXIST));
+
+    Ok(())
+}
+
+#[test]
+fn setxattr_getxattr() -> Result<(), Box<dyn Error>> {
+    let (mut sq, mut cq, _) = urio::new(8)?;
+    let file = tmpfs_file()?;
+    let path = || CString::new(file.path().as_os_str().as_bytes()).unwrap();
+    let name = || CString::new("user.checksum").unwrap();
+
+    // It does not exist yet.
+    sq.alloc_sqe()?
+        .packup_setxattr(path(), name(), b"0".to_vec(), XattrFlags::REPLACE);
+    sq.submit_and_wait(1)?;
+    let err = cq.reap_cqe()?.result().unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ENODATA));
+
+    for (value, flags) in [
+        (&b"0"[..], XattrFlags::empty()),
+        (&b"1234"[..], XattrFlags::REPLACE),
+    ] {
+        sq.alloc_sqe()?
+            .packup_setxattr(path(), name(), value.to_vec(), flags);
+        let submitted = sq.submit_and_wait(1)?;
+        assert_eq!(submitted, 1);
+        cq.reap_cqe()?.result()?;
+    }
+
+    sq.alloc_sqe()?
+        .packup_getxattr(path(), name(), vec![0; 16].into_boxed_slice());
+    sq.submit_and_wait(1)?;
+    let mut cqe = cq.reap_cqe()?;
+    let size = cqe.result()? as usize;
+    let value = cqe.take_buf::<Box<[u8]>>().unwrap();
+    assert_eq!(&value[..size], b"1234");
+
+    // A too small buffer.
+    sq.alloc_sqe()?
+        .packup_getxattr(path(), name(), Vec::with_capacity(2));
+    sq.submit_and_wait(1)?;
+    let mut cqe = cq.reap_cqe()?;
+    let err = cqe.result().unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ERANGE));
+    assert_eq!(cqe.take_buf::<Vec<u8>>().unwrap(), b"");
+
+    Ok(())
+}